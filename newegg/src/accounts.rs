@@ -0,0 +1,245 @@
+//! Clients of several seller accounts and marketplaces, sharing one HTTP
+//! connection pool. Rate limit buckets are not shared: Newegg throttles
+//! each seller account separately, so every client gets buckets of its own
+//! with the registry's default quotas, and one busy account cannot use up
+//! the requests of the others.
+
+use futures::future::join_all;
+use futures::TryStreamExt;
+use reqwest::Client;
+use std::future::Future;
+
+use crate::client::{NeweggClient, NeweggClientBuilder, NeweggPlatform, DEFAULT_TIMEOUT};
+use crate::config::ConfigFile;
+use crate::order::{GetOrderInfoRequest, OrderApi, OrderInfo, OrderInfoStreamOptions, OrderStatus};
+use crate::rate_limit::RateLimiter;
+use crate::result::{NeweggError, NeweggResult};
+
+/// Identifies a client of `NeweggAccounts`, the platform is the marketplace
+/// (`Newegg`, `NeweggBusiness` or `NeweggCanada`) even for sandbox clients,
+/// which are told apart by `sandbox`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountKey {
+  pub platform: NeweggPlatform,
+  pub sandbox: bool,
+  pub seller_id: String,
+}
+
+impl AccountKey {
+  pub fn new(platform: NeweggPlatform, seller_id: &str) -> Self {
+    AccountKey {
+      platform: platform.marketplace(),
+      sandbox: platform.is_sandbox(),
+      seller_id: seller_id.to_string(),
+    }
+  }
+
+  fn of(client: &NeweggClient) -> Self {
+    AccountKey {
+      platform: client.get_platform(),
+      sandbox: client.is_sandbox(),
+      seller_id: client.seller_id().to_string(),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct AccountResult<T> {
+  pub account: AccountKey,
+  pub result: NeweggResult<T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketplaceOrder {
+  pub account: AccountKey,
+  pub order: OrderInfo,
+}
+
+/// Orders of every account, with the errors of the accounts that failed.
+#[derive(Debug, Default)]
+pub struct MarketplaceOrders {
+  pub orders: Vec<MarketplaceOrder>,
+  pub errors: Vec<(AccountKey, NeweggError)>,
+}
+
+pub struct NeweggAccounts {
+  http: Client,
+  default_quotas: RateLimiter,
+  clients: Vec<NeweggClient>,
+}
+
+impl NeweggAccounts {
+  pub fn new() -> NeweggResult<Self> {
+    let http = Client::builder().timeout(DEFAULT_TIMEOUT).build()?;
+    Ok(Self::with_http_client(http))
+  }
+
+  /// Please make sure your HTTP client has very long timeout
+  /// because some APIs are very slow
+  pub fn with_http_client(http: Client) -> Self {
+    NeweggAccounts {
+      http,
+      default_quotas: RateLimiter::new(),
+      clients: vec![],
+    }
+  }
+
+  /// Quotas of the clients added afterwards without a limiter of their own.
+  /// Only the quotas of `limiter` are used, its buckets are not shared.
+  pub fn with_default_quotas(self, limiter: RateLimiter) -> Self {
+    Self {
+      default_quotas: limiter,
+      ..self
+    }
+  }
+
+  /// A registry with a client for every profile of `config`.
+  pub fn from_config(config: &ConfigFile) -> NeweggResult<Self> {
+    let mut accounts = Self::new()?;
+    for name in config.profile_names() {
+      let profile = config.profile(Some(name))?;
+      accounts.add(NeweggClientBuilder::from_profile(profile)?)?;
+    }
+    Ok(accounts)
+  }
+
+  pub fn http_client(&self) -> &Client {
+    &self.http
+  }
+
+  pub fn default_quotas(&self) -> &RateLimiter {
+    &self.default_quotas
+  }
+
+  /// Builds and registers a client, replacing the one of the same account.
+  ///
+  /// The client uses the shared HTTP client unless `builder` sets a timeout,
+  /// user agent, proxy or HTTP client, and new buckets with the default
+  /// quotas unless it sets a limiter.
+  pub fn add(&mut self, builder: NeweggClientBuilder) -> NeweggResult<&NeweggClient> {
+    let client = builder
+      .share(&self.http, &self.default_quotas.unshared())
+      .build()?;
+    Ok(self.insert(client))
+  }
+
+  /// Registers a client as is, replacing the one of the same account.
+  pub fn insert(&mut self, client: NeweggClient) -> &NeweggClient {
+    let key = AccountKey::of(&client);
+    let index = match self.clients.iter().position(|c| AccountKey::of(c) == key) {
+      Some(index) => {
+        self.clients[index] = client;
+        index
+      }
+      None => {
+        self.clients.push(client);
+        self.clients.len() - 1
+      }
+    };
+    &self.clients[index]
+  }
+
+  pub fn get(&self, platform: NeweggPlatform, seller_id: &str) -> Option<&NeweggClient> {
+    let key = AccountKey::new(platform, seller_id);
+    self.clients.iter().find(|c| AccountKey::of(c) == key)
+  }
+
+  pub fn remove(&mut self, platform: NeweggPlatform, seller_id: &str) -> Option<NeweggClient> {
+    let key = AccountKey::new(platform, seller_id);
+    let index = self.clients.iter().position(|c| AccountKey::of(c) == key)?;
+    Some(self.clients.remove(index))
+  }
+
+  /// Clients in the order they were added.
+  pub fn clients(&self) -> &[NeweggClient] {
+    &self.clients
+  }
+
+  pub fn accounts(&self) -> Vec<AccountKey> {
+    self.clients.iter().map(AccountKey::of).collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.clients.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.clients.is_empty()
+  }
+
+  /// Runs `f` for every client concurrently, results are in the order the
+  /// clients were added.
+  pub async fn for_each<'a, F, Fut, T>(&'a self, f: F) -> Vec<AccountResult<T>>
+  where
+    F: Fn(&'a NeweggClient) -> Fut,
+    Fut: Future<Output = NeweggResult<T>> + 'a,
+  {
+    let results = join_all(self.clients.iter().map(&f)).await;
+    self
+      .clients
+      .iter()
+      .zip(results)
+      .map(|(client, result)| AccountResult {
+        account: AccountKey::of(client),
+        result,
+      })
+      .collect()
+  }
+
+  /// Every order matching `request` from every account, fetched concurrently.
+  pub async fn get_orders(
+    &self,
+    request: &GetOrderInfoRequest,
+    options: OrderInfoStreamOptions,
+  ) -> MarketplaceOrders {
+    let results = self
+      .for_each(|client| {
+        client
+          .get_order_info_stream(request.clone(), options)
+          .try_collect::<Vec<_>>()
+      })
+      .await;
+    let mut merged = MarketplaceOrders::default();
+    for AccountResult { account, result } in results {
+      match result {
+        Ok(orders) => merged
+          .orders
+          .extend(orders.into_iter().map(|order| MarketplaceOrder {
+            account: account.clone(),
+            order,
+          })),
+        Err(err) => merged.errors.push((account, err)),
+      }
+    }
+    merged
+  }
+
+  /// Unshipped orders from every account, fetched concurrently.
+  pub async fn get_unshipped_orders(&self, options: OrderInfoStreamOptions) -> MarketplaceOrders {
+    let request = GetOrderInfoRequest::new()
+      .order_status(OrderStatus::Unshipped)
+      .finalize();
+    self.get_orders(&request, options).await
+  }
+}
+
+#[test]
+fn test_sandbox_accounts() {
+  let mut accounts = NeweggAccounts::new().unwrap();
+  for platform in &[NeweggPlatform::Newegg, NeweggPlatform::NeweggSandbox] {
+    accounts
+      .add(NeweggClient::builder(*platform, "A001", "token", "key"))
+      .unwrap();
+  }
+  assert_eq!(accounts.len(), 2);
+  assert!(accounts.get(NeweggPlatform::NeweggSandbox, "A001").unwrap().is_sandbox());
+  assert!(!accounts.get(NeweggPlatform::Newegg, "A001").unwrap().is_sandbox());
+  assert_eq!(
+    accounts.accounts()[1],
+    AccountKey {
+      platform: NeweggPlatform::Newegg,
+      sandbox: true,
+      seller_id: "A001".to_string(),
+    }
+  );
+}
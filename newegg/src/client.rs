@@ -1,4 +1,9 @@
+use crate::config::ConfigError;
 use crate::result::{NeweggError, NeweggResult};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::service_status::ServiceStatusDomain;
+use crate::transfer::TransferOptions;
 use reqwest::header::HeaderValue;
 pub use reqwest::RequestBuilder;
 use reqwest::{Client, Response};
@@ -6,13 +11,19 @@ pub use reqwest::Method;
 use reqwest::StatusCode;
 use serde::Deserialize;
 use serde_json;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
 use async_trait::async_trait;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NeweggPlatform {
   Newegg,
   NeweggBusiness,
   NeweggCanada,
+  NeweggSandbox,
+  NeweggBusinessSandbox,
+  NeweggCanadaSandbox,
 }
 
 impl NeweggPlatform {
@@ -21,28 +32,112 @@ impl NeweggPlatform {
       NeweggPlatform::Newegg => "https://api.newegg.com/marketplace",
       NeweggPlatform::NeweggBusiness => "https://api.newegg.com/marketplace/b2b",
       NeweggPlatform::NeweggCanada => "https://api.newegg.com/marketplace/can",
+      NeweggPlatform::NeweggSandbox => "https://api.sandbox.newegg.com/marketplace",
+      NeweggPlatform::NeweggBusinessSandbox => "https://api.sandbox.newegg.com/marketplace/b2b",
+      NeweggPlatform::NeweggCanadaSandbox => "https://api.sandbox.newegg.com/marketplace/can",
     }
   }
+
+  /// The production marketplace, sandbox platforms accept the same requests.
+  pub fn marketplace(&self) -> Self {
+    match *self {
+      NeweggPlatform::NeweggSandbox => NeweggPlatform::Newegg,
+      NeweggPlatform::NeweggBusinessSandbox => NeweggPlatform::NeweggBusiness,
+      NeweggPlatform::NeweggCanadaSandbox => NeweggPlatform::NeweggCanada,
+      other => other,
+    }
+  }
+
+  /// The sandbox environment of the marketplace.
+  pub fn sandbox(&self) -> Self {
+    match *self {
+      NeweggPlatform::Newegg => NeweggPlatform::NeweggSandbox,
+      NeweggPlatform::NeweggBusiness => NeweggPlatform::NeweggBusinessSandbox,
+      NeweggPlatform::NeweggCanada => NeweggPlatform::NeweggCanadaSandbox,
+      other => other,
+    }
+  }
+
+  pub fn is_sandbox(&self) -> bool {
+    self.marketplace() != *self
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match *self {
+      NeweggPlatform::Newegg => "Newegg",
+      NeweggPlatform::NeweggBusiness => "NeweggBusiness",
+      NeweggPlatform::NeweggCanada => "NeweggCanada",
+      NeweggPlatform::NeweggSandbox => "NeweggSandbox",
+      NeweggPlatform::NeweggBusinessSandbox => "NeweggBusinessSandbox",
+      NeweggPlatform::NeweggCanadaSandbox => "NeweggCanadaSandbox",
+    }
+  }
+
+  /// Parses a variant name like `NeweggCanada`, ignoring case.
+  #[allow(clippy::should_implement_trait)]
+  pub fn from_str(v: &str) -> Option<Self> {
+    [
+      NeweggPlatform::Newegg,
+      NeweggPlatform::NeweggBusiness,
+      NeweggPlatform::NeweggCanada,
+      NeweggPlatform::NeweggSandbox,
+      NeweggPlatform::NeweggBusinessSandbox,
+      NeweggPlatform::NeweggCanadaSandbox,
+    ]
+    .iter()
+    .find(|platform| platform.as_str().eq_ignore_ascii_case(v.trim()))
+    .cloned()
+  }
 }
 
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
 pub struct NeweggClient {
   http: Client,
   seller_id_: String,
   token: HeaderValue,
   secret_key: HeaderValue,
   platform: NeweggPlatform,
+  base_url: Option<String>,
+  domain_timeouts: HashMap<ServiceStatusDomain, Duration>,
+  retry_policy: RetryPolicy,
+  rate_limiter: Option<RateLimiter>,
+  transfer_options: TransferOptions,
 }
 
 impl NeweggClient {
+  pub fn builder(
+    platform: NeweggPlatform,
+    seller_id: &str,
+    token: &str,
+    secret_key: &str,
+  ) -> NeweggClientBuilder {
+    NeweggClientBuilder {
+      platform,
+      seller_id: seller_id.to_owned(),
+      token: token.to_owned(),
+      secret_key: secret_key.to_owned(),
+      base_url: None,
+      http: None,
+      timeout: None,
+      connect_timeout: None,
+      domain_timeouts: HashMap::new(),
+      user_agent: None,
+      proxy: None,
+      retry_policy: None,
+      rate_limiter: None,
+      transfer_options: None,
+    }
+  }
+
   pub fn new(
     platform: NeweggPlatform,
     seller_id: &str,
     token: &str,
     secret_key: &str,
   ) -> NeweggResult<Self> {
-    let client = Client::builder()
-      .timeout(::std::time::Duration::from_secs(300))
-      .build()?;
+    let client = Client::builder().timeout(DEFAULT_TIMEOUT).build()?;
     Self::with_http_client(platform, seller_id, token, secret_key, client)
   }
 
@@ -62,26 +157,96 @@ impl NeweggClient {
       secret_key: HeaderValue::from_str(secret_key)
         .map_err(|_| NeweggError::InvalidHeader("SecretKey"))?,
       platform,
+      base_url: None,
+      domain_timeouts: HashMap::new(),
       http,
+      retry_policy: RetryPolicy::default(),
+      rate_limiter: None,
+      transfer_options: TransferOptions::default(),
     })
   }
 
+  /// Sends requests to `base_url` instead of the platform's marketplace URL,
+  /// e.g. a local mock server.
+  pub fn with_base_url(self, base_url: &str) -> Self {
+    Self {
+      base_url: Some(base_url.trim_end_matches('/').to_string()),
+      ..self
+    }
+  }
+
+  pub fn base_url(&self) -> &str {
+    self
+      .base_url
+      .as_deref()
+      .unwrap_or_else(|| self.platform.base_url())
+  }
+
+  pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+    Self {
+      retry_policy,
+      ..self
+    }
+  }
+
+  pub fn retry_policy(&self) -> &RetryPolicy {
+    &self.retry_policy
+  }
+
+  /// Throttles requests per API domain. The limiter can be shared by many clients.
+  pub fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+    Self {
+      rate_limiter: Some(rate_limiter),
+      ..self
+    }
+  }
+
+  pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+    self.rate_limiter.as_ref()
+  }
+
+  /// Connection options for the FTP/SFTP servers hosting report and feed files.
+  pub fn with_transfer_options(self, transfer_options: TransferOptions) -> Self {
+    Self {
+      transfer_options,
+      ..self
+    }
+  }
+
+  pub fn transfer_options(&self) -> &TransferOptions {
+    &self.transfer_options
+  }
+
   pub fn seller_id(&self) -> &str {
     self.seller_id_.as_ref()
   }
 
+  /// The marketplace the client talks to. Sandbox platforms are reported as
+  /// their production marketplace, use `is_sandbox` to tell them apart.
   pub fn get_platform(&self) -> NeweggPlatform {
-    self.platform
+    self.platform.marketplace()
+  }
+
+  pub fn is_sandbox(&self) -> bool {
+    self.platform.is_sandbox()
+  }
+
+  pub(crate) fn http(&self) -> &Client {
+    &self.http
   }
 
   pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
     use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
     let mut b = self
       .http
-      .request(method, &format!("{}{}", self.platform.base_url(), path));
+      .request(method, format!("{}{}", self.base_url(), path));
 
     b = b.query(&[("sellerid", &self.seller_id_ as &str)]);
 
+    if let Some(timeout) = ServiceStatusDomain::from_path(path).and_then(|d| self.domain_timeouts.get(&d)) {
+      b = b.timeout(*timeout);
+    }
+
     let mut headers = HeaderMap::new();
     headers.insert(AUTHORIZATION, self.token.clone());
     headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
@@ -89,6 +254,227 @@ impl NeweggClient {
     headers.insert("SecretKey", self.secret_key.clone());
     b.headers(headers)
   }
+
+  /// Sends an idempotent request, retrying it according to the client's `RetryPolicy`.
+  /// Responses with a non-2xx status are returned as `NeweggError::Request`.
+  pub async fn send(&self, req: RequestBuilder) -> NeweggResult<Response> {
+    self.execute(req, true).await
+  }
+
+  /// Sends a request that changes seller state.
+  /// Only throttled requests are retried unless
+  /// `RetryPolicy::retry_non_idempotent` is set.
+  pub async fn send_non_idempotent(&self, req: RequestBuilder) -> NeweggResult<Response> {
+    self.execute(req, false).await
+  }
+
+  async fn execute(&self, req: RequestBuilder, idempotent: bool) -> NeweggResult<Response> {
+    let req = req.build()?;
+    let domain = ServiceStatusDomain::from_path(req.url().path());
+    let mut attempt = 1;
+    loop {
+      if let (Some(limiter), Some(domain)) = (self.rate_limiter.as_ref(), domain) {
+        limiter.acquire(domain).await;
+      }
+
+      let res = match req.try_clone() {
+        Some(req) => self.http.execute(req).await?,
+        // streaming bodies can not be replayed
+        None => return Ok(self.http.execute(req).await?),
+      };
+
+      let status = res.status();
+      if status.is_success() {
+        return Ok(res);
+      }
+      let url = res.url().to_string();
+      let headers = res.headers().clone();
+      let err = NeweggError::request(&url, status, strip_bom(&res.text().await?));
+      match self.retry_policy.next_delay(attempt, &err, &headers, idempotent) {
+        Some(delay) => tokio::time::sleep(delay).await,
+        None => return Err(err),
+      }
+      attempt += 1;
+    }
+  }
+}
+
+/// Builds a `NeweggClient` from code, environment variables or a config
+/// file (see the `config` module).
+pub struct NeweggClientBuilder {
+  platform: NeweggPlatform,
+  seller_id: String,
+  token: String,
+  secret_key: String,
+  base_url: Option<String>,
+  http: Option<Client>,
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  domain_timeouts: HashMap<ServiceStatusDomain, Duration>,
+  user_agent: Option<String>,
+  proxy: Option<String>,
+  retry_policy: Option<RetryPolicy>,
+  rate_limiter: Option<RateLimiter>,
+  transfer_options: Option<TransferOptions>,
+}
+
+impl NeweggClientBuilder {
+  /// Switches to the sandbox (or production) environment of the platform.
+  pub fn sandbox(self, v: bool) -> Self {
+    Self {
+      platform: if v {
+        self.platform.sandbox()
+      } else {
+        self.platform.marketplace()
+      },
+      ..self
+    }
+  }
+
+  /// Overrides the platform's URL, e.g. for an egress proxy or a mock server.
+  /// Request paths like `/ordermgmt/order/orderinfo` are appended to it.
+  pub fn base_url(self, base_url: &str) -> Self {
+    Self {
+      base_url: Some(base_url.to_string()),
+      ..self
+    }
+  }
+
+  /// Uses `http` as is, `timeout`, `connect_timeout`, `user_agent` and
+  /// `proxy` are ignored.
+  ///
+  /// Please make sure your HTTP client has very long timeout
+  /// because some APIs are very slow
+  pub fn http_client(self, http: Client) -> Self {
+    Self {
+      http: Some(http),
+      ..self
+    }
+  }
+
+  /// Request timeout, 300 seconds by default.
+  pub fn timeout(self, v: Duration) -> Self {
+    Self {
+      timeout: Some(v),
+      ..self
+    }
+  }
+
+  pub fn connect_timeout(self, v: Duration) -> Self {
+    Self {
+      connect_timeout: Some(v),
+      ..self
+    }
+  }
+
+  /// Request timeout of one API domain, e.g. a longer one for `reportmgmt`.
+  pub fn domain_timeout(mut self, domain: ServiceStatusDomain, v: Duration) -> Self {
+    self.domain_timeouts.insert(domain, v);
+    self
+  }
+
+  pub fn user_agent(self, v: &str) -> Self {
+    Self {
+      user_agent: Some(v.to_string()),
+      ..self
+    }
+  }
+
+  /// Proxy URL for every request, e.g. `http://proxy.internal:3128`.
+  pub fn proxy(self, v: &str) -> Self {
+    Self {
+      proxy: Some(v.to_string()),
+      ..self
+    }
+  }
+
+  pub fn retry_policy(self, v: RetryPolicy) -> Self {
+    Self {
+      retry_policy: Some(v),
+      ..self
+    }
+  }
+
+  pub fn rate_limiter(self, v: RateLimiter) -> Self {
+    Self {
+      rate_limiter: Some(v),
+      ..self
+    }
+  }
+
+  pub fn transfer_options(self, v: TransferOptions) -> Self {
+    Self {
+      transfer_options: Some(v),
+      ..self
+    }
+  }
+
+  /// Uses `http` unless the builder has its own HTTP options, and
+  /// `rate_limiter` unless it has its own limiter.
+  pub(crate) fn share(self, http: &Client, rate_limiter: &RateLimiter) -> Self {
+    let custom_http = self.http.is_some()
+      || self.timeout.is_some()
+      || self.connect_timeout.is_some()
+      || self.user_agent.is_some()
+      || self.proxy.is_some();
+    Self {
+      http: if custom_http {
+        self.http
+      } else {
+        Some(http.clone())
+      },
+      rate_limiter: self.rate_limiter.or_else(|| Some(rate_limiter.clone())),
+      ..self
+    }
+  }
+
+  pub fn build(self) -> NeweggResult<NeweggClient> {
+    let http = match self.http {
+      Some(http) => http,
+      None => {
+        let mut b = Client::builder().timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT));
+        if let Some(v) = self.connect_timeout {
+          b = b.connect_timeout(v);
+        }
+        if let Some(ref v) = self.user_agent {
+          b = b.user_agent(v.as_str());
+        }
+        if let Some(ref v) = self.proxy {
+          let proxy = reqwest::Proxy::all(v.as_str()).map_err(|_| ConfigError::InvalidValue {
+            key: "proxy".to_string(),
+            value: v.clone(),
+          })?;
+          b = b.proxy(proxy);
+        }
+        b.build()?
+      }
+    };
+    let mut client = NeweggClient::with_http_client(
+      self.platform,
+      &self.seller_id,
+      &self.token,
+      &self.secret_key,
+      http,
+    )?;
+    if let Some(base_url) = self.base_url {
+      Url::parse(&base_url).map_err(|_| ConfigError::InvalidValue {
+        key: "base_url".to_string(),
+        value: base_url.clone(),
+      })?;
+      client = client.with_base_url(&base_url);
+    }
+    client.domain_timeouts = self.domain_timeouts;
+    if let Some(v) = self.retry_policy {
+      client = client.with_retry_policy(v);
+    }
+    if let Some(v) = self.rate_limiter {
+      client = client.with_rate_limiter(v);
+    }
+    if let Some(v) = self.transfer_options {
+      client = client.with_transfer_options(v);
+    }
+    Ok(client)
+  }
 }
 
 #[async_trait]
@@ -98,29 +484,20 @@ pub trait NeweggResponse {
 
 const BOM: char = '\u{feff}';
 
+fn strip_bom(text: &str) -> &str {
+  text.strip_prefix(BOM).unwrap_or(text)
+}
+
 #[async_trait]
 impl NeweggResponse for Response {
-  async  fn get_response<T: for<'de> Deserialize<'de>>(self) -> NeweggResult<T> {
-    let status = self.status().clone();
+  async fn get_response<T: for<'de> Deserialize<'de>>(self) -> NeweggResult<T> {
+    let status = self.status();
     let url = self.url().to_string();
     let text = self.text().await?;
-    let body_str: &str = if let Some(c) = text.chars().next() {
-      // strip BOM
-      if c == BOM {
-        &text[BOM.len_utf8()..]
-      } else {
-        text.as_ref()
-      }
-    } else {
-      ""
-    };
+    let body_str = strip_bom(&text);
 
     if status != StatusCode::OK {
-      Err(NeweggError::Request {
-        path: url,
-        status,
-        body: body_str.to_string(),
-      })
+      Err(NeweggError::request(&url, status, body_str))
     } else {
       match serde_json::from_str(body_str) {
         Ok(v) => Ok(v),
@@ -132,3 +509,24 @@ impl NeweggResponse for Response {
     }
   }
 }
+
+#[test]
+fn test_sandbox_base_url() {
+  let client = NeweggClient::builder(NeweggPlatform::NeweggCanada, "A001", "token", "key")
+    .sandbox(true)
+    .build()
+    .unwrap();
+  assert!(client.is_sandbox());
+  assert_eq!(client.get_platform(), NeweggPlatform::NeweggCanada);
+  assert_eq!(client.base_url(), "https://api.sandbox.newegg.com/marketplace/can");
+
+  let client = NeweggClient::builder(NeweggPlatform::Newegg, "A001", "token", "key")
+    .base_url("http://127.0.0.1:8080/marketplace/")
+    .build()
+    .unwrap();
+  assert_eq!(client.base_url(), "http://127.0.0.1:8080/marketplace");
+  assert!(NeweggClient::builder(NeweggPlatform::Newegg, "A001", "token", "key")
+    .base_url("not a url")
+    .build()
+    .is_err());
+}
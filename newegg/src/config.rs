@@ -0,0 +1,411 @@
+//! Client configuration from environment variables or a TOML/JSON file
+//! with named seller profiles:
+//!
+//! ```toml
+//! default_profile = "us"
+//!
+//! [profiles.us]
+//! platform = "Newegg"
+//! seller_id = "A001"
+//! token = "..."
+//! secret_key = "..."
+//! timeout_secs = 300
+//! domain_timeout_secs = { reportmgmt = 900 }
+//! retry = { max_attempts = 5, base_delay_ms = 500 }
+//! rate_limit = { per_minute = 60, domains = { ordermgmt = 20 } }
+//!
+//! [profiles.ca]
+//! platform = "NeweggCanada"
+//! sandbox = true
+//! seller_id = "A002"
+//! token = "..."
+//! secret_key = "..."
+//! ```
+
+use serde_derive::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::client::{NeweggClient, NeweggClientBuilder, NeweggPlatform};
+use crate::rate_limit::{RateLimitQuota, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::service_status::ServiceStatusDomain;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+  #[error("missing config value: {0}")]
+  Missing(String),
+
+  #[error("invalid config value: {key} = '{value}'")]
+  InvalidValue { key: String, value: String },
+
+  #[error("unknown profile: {0}")]
+  UnknownProfile(String),
+
+  #[error("unsupported config file format: {0}")]
+  UnsupportedFormat(String),
+
+  #[error("read config file error: {0}")]
+  Io(#[from] std::io::Error),
+
+  #[error("parse toml error: {0}")]
+  Toml(#[from] toml::de::Error),
+
+  #[error("parse json error: {0}")]
+  Json(#[from] serde_json::Error),
+}
+
+pub type ConfigResult<T> = ::std::result::Result<T, ConfigError>;
+
+/// Named seller profiles, `Debug` hides the credentials of every profile.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigFile {
+  /// Profile used when none is asked for, the only one if unset
+  #[serde(default)]
+  pub default_profile: Option<String>,
+  pub profiles: HashMap<String, ClientProfile>,
+}
+
+impl ConfigFile {
+  /// Loads a `.toml` or `.json` file.
+  pub fn load(path: &Path) -> ConfigResult<Self> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+      Some("toml") => Self::from_toml(&content),
+      Some("json") => Self::from_json(&content),
+      _ => Err(ConfigError::UnsupportedFormat(path.display().to_string())),
+    }
+  }
+
+  pub fn from_toml(content: &str) -> ConfigResult<Self> {
+    Ok(toml::from_str(content)?)
+  }
+
+  pub fn from_json(content: &str) -> ConfigResult<Self> {
+    Ok(serde_json::from_str(content)?)
+  }
+
+  /// The named profile, or the default one if `name` is `None`.
+  pub fn profile(&self, name: Option<&str>) -> ConfigResult<&ClientProfile> {
+    let name = match name.or(self.default_profile.as_deref()) {
+      Some(name) => name,
+      None if self.profiles.len() == 1 => return Ok(self.profiles.values().next().unwrap()),
+      None => return Err(ConfigError::Missing("default_profile".to_string())),
+    };
+    self
+      .profiles
+      .get(name)
+      .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))
+  }
+
+  pub fn profile_names(&self) -> Vec<&str> {
+    let mut names: Vec<&str> = self.profiles.keys().map(AsRef::as_ref).collect();
+    names.sort_unstable();
+    names
+  }
+}
+
+/// Credentials and client options of one seller account.
+#[derive(Clone, Deserialize)]
+pub struct ClientProfile {
+  /// `Newegg`, `NeweggBusiness`, `NeweggCanada` or one of the sandbox variants
+  pub platform: String,
+  pub seller_id: String,
+  pub token: String,
+  pub secret_key: String,
+  #[serde(default)]
+  pub sandbox: bool,
+  pub base_url: Option<String>,
+  pub user_agent: Option<String>,
+  pub proxy: Option<String>,
+  pub timeout_secs: Option<u64>,
+  pub connect_timeout_secs: Option<u64>,
+  /// API domain (`ordermgmt`, `reportmgmt`, ...) → request timeout
+  #[serde(default)]
+  pub domain_timeout_secs: HashMap<String, u64>,
+  pub retry: Option<RetryConfig>,
+  pub rate_limit: Option<RateLimitConfig>,
+}
+
+// keeps `token` and `secret_key` out of logs
+impl fmt::Debug for ClientProfile {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("ClientProfile")
+      .field("platform", &self.platform)
+      .field("seller_id", &self.seller_id)
+      .field("token", &"<redacted>")
+      .field("secret_key", &"<redacted>")
+      .field("sandbox", &self.sandbox)
+      .field("base_url", &self.base_url)
+      .field("user_agent", &self.user_agent)
+      .field("proxy", &self.proxy)
+      .field("timeout_secs", &self.timeout_secs)
+      .field("connect_timeout_secs", &self.connect_timeout_secs)
+      .field("domain_timeout_secs", &self.domain_timeout_secs)
+      .field("retry", &self.retry)
+      .field("rate_limit", &self.rate_limit)
+      .finish()
+  }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RetryConfig {
+  pub max_attempts: Option<u32>,
+  pub base_delay_ms: Option<u64>,
+  pub max_delay_ms: Option<u64>,
+  pub retry_non_idempotent: Option<bool>,
+}
+
+impl RetryConfig {
+  pub fn to_policy(&self) -> RetryPolicy {
+    let mut policy = RetryPolicy::default();
+    if let Some(v) = self.max_attempts {
+      policy = policy.max_attempts(v);
+    }
+    if let Some(v) = self.base_delay_ms {
+      policy = policy.base_delay(Duration::from_millis(v));
+    }
+    if let Some(v) = self.max_delay_ms {
+      policy = policy.max_delay(Duration::from_millis(v));
+    }
+    if let Some(v) = self.retry_non_idempotent {
+      policy = policy.retry_non_idempotent(v);
+    }
+    policy
+  }
+}
+
+/// Requests per minute, for every domain and per API domain.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RateLimitConfig {
+  pub per_minute: Option<u32>,
+  #[serde(default)]
+  pub domains: HashMap<String, u32>,
+}
+
+impl RateLimitConfig {
+  pub fn to_limiter(&self) -> ConfigResult<RateLimiter> {
+    let mut limiter = match self.per_minute {
+      Some(v) => RateLimiter::with_default_quota(per_minute("rate_limit.per_minute", v)?),
+      None => RateLimiter::new(),
+    };
+    for (domain, &v) in &self.domains {
+      limiter = limiter.quota(
+        parse_domain("rate_limit.domains", domain)?,
+        per_minute("rate_limit.domains", v)?,
+      );
+    }
+    Ok(limiter)
+  }
+}
+
+/// Zero would mean "no limit", which is not what a config file asks for.
+fn per_minute(key: &str, v: u32) -> ConfigResult<RateLimitQuota> {
+  if v == 0 {
+    return Err(ConfigError::InvalidValue {
+      key: key.to_string(),
+      value: v.to_string(),
+    });
+  }
+  Ok(RateLimitQuota::per_minute(v))
+}
+
+fn parse_domain(key: &str, v: &str) -> ConfigResult<ServiceStatusDomain> {
+  ServiceStatusDomain::from_str(v).ok_or_else(|| ConfigError::InvalidValue {
+    key: key.to_string(),
+    value: v.to_string(),
+  })
+}
+
+fn parse_platform(key: &str, v: &str) -> ConfigResult<NeweggPlatform> {
+  NeweggPlatform::from_str(v).ok_or_else(|| ConfigError::InvalidValue {
+    key: key.to_string(),
+    value: v.to_string(),
+  })
+}
+
+impl NeweggClientBuilder {
+  pub fn from_profile(profile: &ClientProfile) -> ConfigResult<Self> {
+    let platform = parse_platform("platform", &profile.platform)?;
+    let mut b = NeweggClient::builder(
+      platform,
+      &profile.seller_id,
+      &profile.token,
+      &profile.secret_key,
+    );
+    if profile.sandbox {
+      b = b.sandbox(true);
+    }
+    if let Some(ref v) = profile.base_url {
+      b = b.base_url(v);
+    }
+    if let Some(ref v) = profile.user_agent {
+      b = b.user_agent(v);
+    }
+    if let Some(ref v) = profile.proxy {
+      b = b.proxy(v);
+    }
+    if let Some(v) = profile.timeout_secs {
+      b = b.timeout(Duration::from_secs(v));
+    }
+    if let Some(v) = profile.connect_timeout_secs {
+      b = b.connect_timeout(Duration::from_secs(v));
+    }
+    for (domain, &v) in &profile.domain_timeout_secs {
+      b = b.domain_timeout(
+        parse_domain("domain_timeout_secs", domain)?,
+        Duration::from_secs(v),
+      );
+    }
+    if let Some(ref v) = profile.retry {
+      b = b.retry_policy(v.to_policy());
+    }
+    if let Some(ref v) = profile.rate_limit {
+      b = b.rate_limiter(v.to_limiter()?);
+    }
+    Ok(b)
+  }
+
+  /// Reads profile `name`, or the default profile, from a `.toml` or `.json` file.
+  pub fn from_file(path: &Path, name: Option<&str>) -> ConfigResult<Self> {
+    Self::from_profile(ConfigFile::load(path)?.profile(name)?)
+  }
+
+  /// Reads `NEWEGG_PLATFORM`, `NEWEGG_SELLER_ID`, `NEWEGG_TOKEN` and
+  /// `NEWEGG_SECRET_KEY`, see `from_env_prefixed`.
+  pub fn from_env() -> ConfigResult<Self> {
+    Self::from_env_prefixed("NEWEGG_")
+  }
+
+  /// Reads `<prefix>PLATFORM`, `<prefix>SELLER_ID`, `<prefix>TOKEN` and
+  /// `<prefix>SECRET_KEY`, plus the optional `<prefix>SANDBOX`,
+  /// `<prefix>BASE_URL`, `<prefix>USER_AGENT`, `<prefix>PROXY` and
+  /// `<prefix>TIMEOUT_SECS`.
+  pub fn from_env_prefixed(prefix: &str) -> ConfigResult<Self> {
+    Self::from_vars(prefix, |name| env::var(name).ok())
+  }
+
+  /// Same as `from_env_prefixed`, with the variables looked up by `var`.
+  pub fn from_vars<F>(prefix: &str, var: F) -> ConfigResult<Self>
+  where
+    F: Fn(&str) -> Option<String>,
+  {
+    let get = |name: &str| var(&format!("{}{}", prefix, name));
+    let require =
+      |name: &str| get(name).ok_or_else(|| ConfigError::Missing(format!("{}{}", prefix, name)));
+    let invalid = |name: &str, value: String| ConfigError::InvalidValue {
+      key: format!("{}{}", prefix, name),
+      value,
+    };
+
+    let platform = require("PLATFORM")?;
+    let platform = parse_platform(&format!("{}PLATFORM", prefix), &platform)?;
+    let mut b = NeweggClient::builder(
+      platform,
+      &require("SELLER_ID")?,
+      &require("TOKEN")?,
+      &require("SECRET_KEY")?,
+    );
+    if let Some(v) = get("SANDBOX") {
+      match v.to_lowercase().as_ref() {
+        "1" | "true" | "yes" => b = b.sandbox(true),
+        "0" | "false" | "no" | "" => {}
+        _ => return Err(invalid("SANDBOX", v)),
+      }
+    }
+    if let Some(v) = get("BASE_URL") {
+      b = b.base_url(&v);
+    }
+    if let Some(v) = get("USER_AGENT") {
+      b = b.user_agent(&v);
+    }
+    if let Some(v) = get("PROXY") {
+      b = b.proxy(&v);
+    }
+    if let Some(v) = get("TIMEOUT_SECS") {
+      let secs = v.parse().map_err(|_| invalid("TIMEOUT_SECS", v.clone()))?;
+      b = b.timeout(Duration::from_secs(secs));
+    }
+    Ok(b)
+  }
+}
+
+#[test]
+fn test_client_config_sources() {
+  let config = ConfigFile::from_toml(
+    r#"
+    default_profile = "us"
+
+    [profiles.us]
+    platform = "Newegg"
+    seller_id = "A001"
+    token = "token"
+    secret_key = "key"
+    domain_timeout_secs = { reportmgmt = 900 }
+    retry = { max_attempts = 5 }
+    rate_limit = { per_minute = 60, domains = { ordermgmt = 20 } }
+
+    [profiles.ca]
+    platform = "neweggcanada"
+    sandbox = true
+    seller_id = "A002"
+    token = "token"
+    secret_key = "key"
+    "#,
+  )
+  .unwrap();
+  assert_eq!(config.profile_names(), vec!["ca", "us"]);
+  let debug = format!("{:?}", config);
+  assert!(debug.contains("A001") && !debug.contains("\"token\"") && !debug.contains("\"key\""));
+  let client = NeweggClientBuilder::from_profile(config.profile(None).unwrap())
+    .unwrap()
+    .build()
+    .unwrap();
+  assert_eq!(client.seller_id(), "A001");
+  assert_eq!(client.retry_policy().max_attempts, 5);
+  let client = NeweggClientBuilder::from_profile(config.profile(Some("ca")).unwrap())
+    .unwrap()
+    .build()
+    .unwrap();
+  assert!(client.is_sandbox());
+  assert!(matches!(
+    config.profile(Some("uk")),
+    Err(ConfigError::UnknownProfile(_))
+  ));
+  let rate_limit = RateLimitConfig {
+    per_minute: Some(0),
+    ..Default::default()
+  };
+  match rate_limit.to_limiter() {
+    Err(ConfigError::InvalidValue { key, .. }) => assert_eq!(key, "rate_limit.per_minute"),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+
+  let vars: HashMap<&str, &str> = vec![
+    ("X_PLATFORM", "NeweggBusiness"),
+    ("X_SELLER_ID", "A003"),
+    ("X_TOKEN", "token"),
+    ("X_SECRET_KEY", "key"),
+    ("X_TIMEOUT_SECS", "soon"),
+  ]
+  .into_iter()
+  .collect();
+  let from_vars = |vars: &HashMap<&str, &str>| {
+    NeweggClientBuilder::from_vars("X_", |name| vars.get(name).map(ToString::to_string))
+  };
+  match from_vars(&vars) {
+    Err(ConfigError::InvalidValue { key, .. }) => assert_eq!(key, "X_TIMEOUT_SECS"),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+  let mut vars = vars;
+  vars.remove("X_TIMEOUT_SECS");
+  vars.remove("X_TOKEN");
+  match from_vars(&vars) {
+    Err(ConfigError::Missing(name)) => assert_eq!(name, "X_TOKEN"),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+}
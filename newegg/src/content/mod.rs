@@ -0,0 +1,84 @@
+use crate::client::*;
+use crate::result::NeweggResult;
+use async_trait::async_trait;
+
+mod types;
+
+pub use self::types::*;
+
+#[async_trait]
+pub trait ContentApi {
+  async fn get_item_inventory(
+    &self,
+    request: &GetItemInventoryRequest,
+  ) -> NeweggResult<GetItemInventoryResponse>;
+  async fn update_item_inventory(
+    &self,
+    request: &UpdateItemInventoryRequest,
+  ) -> NeweggResult<UpdateItemInventoryResponse>;
+  async fn get_item_price(&self, request: &GetItemPriceRequest) -> NeweggResult<GetItemPriceResponse>;
+  async fn update_item_price(
+    &self,
+    request: &UpdateItemPriceRequest,
+  ) -> NeweggResult<UpdateItemPriceResponse>;
+}
+
+#[async_trait]
+impl ContentApi for NeweggClient {
+  async fn get_item_inventory(
+    &self,
+    request: &GetItemInventoryRequest,
+  ) -> NeweggResult<GetItemInventoryResponse> {
+    self
+      .send(
+        self
+          .request(Method::POST, "/contentmgmt/item/international/inventory")
+          .json(request),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn update_item_inventory(
+    &self,
+    request: &UpdateItemInventoryRequest,
+  ) -> NeweggResult<UpdateItemInventoryResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/contentmgmt/item/international/inventory")
+          .json(request),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_item_price(&self, request: &GetItemPriceRequest) -> NeweggResult<GetItemPriceResponse> {
+    self
+      .send(
+        self
+          .request(Method::POST, "/contentmgmt/item/international/price")
+          .json(request),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn update_item_price(
+    &self,
+    request: &UpdateItemPriceRequest,
+  ) -> NeweggResult<UpdateItemPriceResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/contentmgmt/item/international/price")
+          .json(request),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+}
@@ -0,0 +1,272 @@
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+
+/// How `Value` identifies an item in content management requests.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ItemIdentifierType {
+  #[serde(rename = "0")]
+  NeweggItemNumber,
+  #[serde(rename = "1")]
+  SellerPartNumber,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/item_management/get_inventory/
+#[derive(Debug, Serialize, Clone)]
+pub struct GetItemInventoryRequest {
+  #[serde(rename = "Type")]
+  pub type_: ItemIdentifierType,
+  #[serde(rename = "Value")]
+  pub value: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "WarehouseList")]
+  pub warehouse_list: Option<Vec<String>>,
+}
+
+impl GetItemInventoryRequest {
+  pub fn new(type_: ItemIdentifierType, value: &str) -> Self {
+    GetItemInventoryRequest {
+      type_,
+      value: value.to_owned(),
+      warehouse_list: None,
+    }
+  }
+
+  pub fn warehouse_list(self, v: Vec<String>) -> Self {
+    Self {
+      warehouse_list: Some(v),
+      ..self
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetItemInventoryResponse {
+  #[serde(rename = "SellerID")]
+  pub seller_id: String,
+  #[serde(rename = "ItemNumber")]
+  pub item_number: Option<String>,
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "InventoryAllocation")]
+  #[serde(default)]
+  pub inventory_allocation: Vec<InventoryAllocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryAllocation {
+  #[serde(rename = "WarehouseLocation")]
+  pub warehouse_location: String,
+  #[serde(rename = "AvailableQuantity")]
+  pub available_quantity: i64,
+  #[serde(rename = "FulfillmentOption")]
+  pub fulfillment_option: Option<String>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/item_management/update_inventory/
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateItemInventoryRequest {
+  #[serde(rename = "Type")]
+  pub type_: ItemIdentifierType,
+  #[serde(rename = "Value")]
+  pub value: String,
+  #[serde(rename = "InventoryList")]
+  pub inventory_list: Vec<InventoryListItem>,
+}
+
+impl UpdateItemInventoryRequest {
+  pub fn new(type_: ItemIdentifierType, value: &str) -> Self {
+    UpdateItemInventoryRequest {
+      type_,
+      value: value.to_owned(),
+      inventory_list: vec![],
+    }
+  }
+
+  pub fn add_inventory(mut self, warehouse_location: &str, available_quantity: i64) -> Self {
+    self.inventory_list.push(InventoryListItem {
+      warehouse_location: warehouse_location.to_owned(),
+      available_quantity,
+    });
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryListItem {
+  #[serde(rename = "WarehouseLocation")]
+  pub warehouse_location: String,
+  #[serde(rename = "AvailableQuantity")]
+  pub available_quantity: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateItemInventoryResponse {
+  #[serde(rename = "SellerID")]
+  pub seller_id: String,
+  #[serde(rename = "ItemNumber")]
+  pub item_number: Option<String>,
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "InventoryList")]
+  #[serde(default)]
+  pub inventory_list: Vec<InventoryListItem>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/item_management/get_price/
+#[derive(Debug, Serialize, Clone)]
+pub struct GetItemPriceRequest {
+  #[serde(rename = "Type")]
+  pub type_: ItemIdentifierType,
+  #[serde(rename = "Value")]
+  pub value: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CountryList")]
+  pub country_list: Option<Vec<String>>,
+}
+
+impl GetItemPriceRequest {
+  pub fn new(type_: ItemIdentifierType, value: &str) -> Self {
+    GetItemPriceRequest {
+      type_,
+      value: value.to_owned(),
+      country_list: None,
+    }
+  }
+
+  pub fn country_list(self, v: Vec<String>) -> Self {
+    Self {
+      country_list: Some(v),
+      ..self
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetItemPriceResponse {
+  #[serde(rename = "ItemNumber")]
+  pub item_number: Option<String>,
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "PriceList")]
+  #[serde(default)]
+  pub price_list: Vec<ItemPrice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemPrice {
+  #[serde(rename = "CountryCode")]
+  pub country_code: String,
+  #[serde(rename = "Currency")]
+  pub currency: String,
+  #[serde(rename = "Active")]
+  pub active: i32,
+  #[serde(rename = "MSRP")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(rename = "MAP")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub map: Option<BigDecimal>,
+  #[serde(rename = "CheckoutMAP")]
+  pub checkout_map: Option<i32>,
+  #[serde(rename = "SellingPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub selling_price: Option<BigDecimal>,
+  #[serde(rename = "EnableFreeShipping")]
+  pub enable_free_shipping: Option<i32>,
+  #[serde(rename = "LimitQuantity")]
+  pub limit_quantity: Option<i64>,
+  #[serde(rename = "OnPromotion")]
+  pub on_promotion: Option<i32>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/item_management/update_price/
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateItemPriceRequest {
+  #[serde(rename = "Type")]
+  pub type_: ItemIdentifierType,
+  #[serde(rename = "Value")]
+  pub value: String,
+  #[serde(rename = "PriceList")]
+  pub price_list: Vec<UpdateItemPrice>,
+}
+
+impl UpdateItemPriceRequest {
+  pub fn new(type_: ItemIdentifierType, value: &str) -> Self {
+    UpdateItemPriceRequest {
+      type_,
+      value: value.to_owned(),
+      price_list: vec![],
+    }
+  }
+
+  pub fn add_price(mut self, price: UpdateItemPrice) -> Self {
+    self.price_list.push(price);
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateItemPrice {
+  #[serde(rename = "CountryCode")]
+  pub country_code: String,
+  #[serde(rename = "Currency")]
+  pub currency: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Active")]
+  pub active: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MSRP")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MAP")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub map: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CheckoutMAP")]
+  pub checkout_map: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellingPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub selling_price: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "EnableFreeShipping")]
+  pub enable_free_shipping: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateItemPriceResponse {
+  #[serde(rename = "ItemNumber")]
+  pub item_number: Option<String>,
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "PriceList")]
+  #[serde(default)]
+  pub price_list: Vec<UpdateItemPrice>,
+}
+
+#[test]
+fn test_item_price_decimal() {
+  let price = UpdateItemPrice {
+    country_code: "USA".to_string(),
+    currency: "USD".to_string(),
+    msrp: Some("24.90".parse().unwrap()),
+    selling_price: Some("19.99".parse().unwrap()),
+    ..Default::default()
+  };
+  let req = UpdateItemPriceRequest::new(ItemIdentifierType::SellerPartNumber, "sku-1").add_price(price);
+  assert_eq!(
+    serde_json::to_string(&req).unwrap(),
+    r#"{"Type":"1","Value":"sku-1","PriceList":[{"CountryCode":"USA","Currency":"USD","MSRP":24.9,"SellingPrice":19.99}]}"#
+  );
+
+  let res: GetItemPriceResponse = serde_json::from_str(
+    r#"{"ItemNumber":"9SIA0001","SellerPartNumber":"sku-1","PriceList":[{"CountryCode":"USA",
+    "Currency":"USD","Active":1,"MSRP":0.3,"MAP":null,"SellingPrice":"19.99"}]}"#,
+  )
+  .unwrap();
+  let price = &res.price_list[0];
+  assert_eq!(price.msrp, Some("0.3".parse().unwrap()));
+  assert_eq!(price.map, None);
+  assert_eq!(price.selling_price.as_ref().unwrap().to_string(), "19.99");
+}
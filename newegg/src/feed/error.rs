@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{ErrorList, ProcessingReport, ProcessingResult};
+
+/// One entry of a processing result's `ErrorList`, e.g.
+/// `CEI0001:Can't find this item in the system, please create it first`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedError {
+  /// Empty if the description has no code prefix
+  pub code: String,
+  pub message: String,
+}
+
+/// Kind of a feed error code. Only `ItemNotFound` has a code in
+/// `FEED_ERROR_CODES`, the other kinds are assigned with `FeedErrorCodes::code`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedErrorKind {
+  /// The seller part number does not exist, create the item first
+  ItemNotFound,
+  ItemAlreadyExists,
+  InvalidPrice,
+  /// Selling price is below the MAP
+  MapViolation,
+  InvalidInventory,
+  InvalidField,
+  /// The item is locked by another running feed
+  ItemLocked,
+  /// Newegg failed to process the record, submitting it again may work
+  SystemError,
+  /// A code missing from `FEED_ERROR_CODES`, empty if the description has none
+  Other(String),
+}
+
+/// Newegg feed error codes and their kinds. Only codes confirmed from
+/// Newegg's processing reports are listed, anything else decodes as
+/// `FeedErrorKind::Other` with its code.
+pub const FEED_ERROR_CODES: &[(&str, FeedErrorKind)] = &[
+  // Can't find this item in the system, please create it first
+  ("CEI0001", FeedErrorKind::ItemNotFound),
+];
+
+impl FeedErrorKind {
+  /// The kind of `code` in `FEED_ERROR_CODES`.
+  pub fn from_code(code: &str) -> Self {
+    FEED_ERROR_CODES
+      .iter()
+      .find(|(c, _)| *c == code)
+      .map(|(_, kind)| kind.clone())
+      .unwrap_or_else(|| FeedErrorKind::Other(code.to_string()))
+  }
+
+  /// Whether submitting the same record again later can succeed.
+  pub fn is_retryable(&self) -> bool {
+    matches!(self, FeedErrorKind::ItemLocked | FeedErrorKind::SystemError)
+  }
+}
+
+/// `FEED_ERROR_CODES` plus codes found in the seller's own processing reports.
+#[derive(Debug, Clone)]
+pub struct FeedErrorCodes {
+  codes: HashMap<String, FeedErrorKind>,
+}
+
+impl Default for FeedErrorCodes {
+  fn default() -> Self {
+    FeedErrorCodes {
+      codes: FEED_ERROR_CODES
+        .iter()
+        .map(|(code, kind)| (code.to_string(), kind.clone()))
+        .collect(),
+    }
+  }
+}
+
+impl FeedErrorCodes {
+  /// Classifies `code` as `kind`, replacing its kind in `FEED_ERROR_CODES`.
+  pub fn code(mut self, code: &str, kind: FeedErrorKind) -> Self {
+    self.codes.insert(code.to_string(), kind);
+    self
+  }
+
+  pub fn kind(&self, code: &str) -> FeedErrorKind {
+    self
+      .codes
+      .get(code)
+      .cloned()
+      .unwrap_or_else(|| FeedErrorKind::Other(code.to_string()))
+  }
+}
+
+impl FeedError {
+  pub fn parse(description: &str) -> Self {
+    let description = description.trim();
+    match description.split_once(':') {
+      Some((code, message)) if is_code(code) => FeedError {
+        code: code.to_string(),
+        message: message.trim().to_string(),
+      },
+      _ => FeedError {
+        code: String::new(),
+        message: description.to_string(),
+      },
+    }
+  }
+
+  pub fn kind(&self) -> FeedErrorKind {
+    FeedErrorKind::from_code(&self.code)
+  }
+
+  pub fn kind_in(&self, codes: &FeedErrorCodes) -> FeedErrorKind {
+    codes.kind(&self.code)
+  }
+
+  /// Whether submitting the same record again later can succeed, per
+  /// `FEED_ERROR_CODES`. Unknown codes are treated as permanent.
+  pub fn is_retryable(&self) -> bool {
+    self.kind().is_retryable()
+  }
+}
+
+fn is_code(v: &str) -> bool {
+  !v.is_empty()
+    && v.len() <= 12
+    && v.chars().all(|c| c.is_ascii_alphanumeric())
+    && v.chars().any(|c| c.is_ascii_digit())
+}
+
+impl fmt::Display for FeedError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.code.is_empty() {
+      f.write_str(&self.message)
+    } else {
+      write!(f, "{}:{}", self.code, self.message)
+    }
+  }
+}
+
+impl ErrorList {
+  pub fn errors(&self) -> Vec<FeedError> {
+    self
+      .error_description
+      .as_slice()
+      .iter()
+      .map(|v| FeedError::parse(v))
+      .collect()
+  }
+}
+
+impl ProcessingResult {
+  pub fn errors(&self) -> Vec<FeedError> {
+    self.error_list.errors()
+  }
+}
+
+impl ProcessingReport {
+  /// Parsed errors of every result, keyed by `SellerPartNumber`.
+  pub fn feed_errors_by_seller_part_number(&self) -> HashMap<String, Vec<FeedError>> {
+    self
+      .errors_by_seller_part_number()
+      .into_iter()
+      .map(|(k, v)| (k, v.iter().map(|v| FeedError::parse(v)).collect()))
+      .collect()
+  }
+}
+
+#[test]
+fn test_parse_feed_error() {
+  let err = FeedError::parse("CEI0001:Can't find this item in the system, please create it first");
+  assert_eq!(err.code, "CEI0001");
+  assert_eq!(err.kind(), FeedErrorKind::ItemNotFound);
+  assert!(!err.is_retryable());
+
+  // the kind comes from the code only, never from the message
+  let err = FeedError::parse("Selling price can not be lower than MAP: 19.99");
+  assert_eq!(err.code, "");
+  assert_eq!(err.kind(), FeedErrorKind::Other(String::new()));
+
+  let err = FeedError::parse("SE0003:System error, please try again later");
+  assert_eq!(err.kind(), FeedErrorKind::Other("SE0003".to_string()));
+  assert!(!err.is_retryable());
+  assert_eq!(err.to_string(), "SE0003:System error, please try again later");
+}
+
+#[test]
+fn test_feed_error_codes() {
+  let codes = FeedErrorCodes::default()
+    .code("SE0003", FeedErrorKind::SystemError)
+    .code("PE0007", FeedErrorKind::MapViolation);
+
+  let err = FeedError::parse("SE0003:System error, please try again later");
+  assert_eq!(err.kind_in(&codes), FeedErrorKind::SystemError);
+  assert!(err.kind_in(&codes).is_retryable());
+
+  let err = FeedError::parse("PE0007:Selling price can not be lower than MAP");
+  assert_eq!(err.kind_in(&codes), FeedErrorKind::MapViolation);
+  assert!(!err.kind_in(&codes).is_retryable());
+
+  let err = FeedError::parse("CEI0001:Can't find this item in the system, please create it first");
+  assert_eq!(err.kind_in(&codes), FeedErrorKind::ItemNotFound);
+  assert!(!err.kind_in(&codes).is_retryable());
+  assert_eq!(
+    FeedError::parse("XX0001:Unknown").kind_in(&codes),
+    FeedErrorKind::Other("XX0001".to_string())
+  );
+  assert!(FeedErrorKind::ItemLocked.is_retryable());
+}
@@ -1,4 +1,29 @@
-use serde_derive::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::RequestEnvelope;
+use crate::order::{CancelOrderReasonCode, ShipOrderAction};
+
+/// A feed message that knows its `requesttype` and envelope schema,
+/// see `FeedApi::submit_feed_message`.
+pub trait FeedMessage: Serialize + Send + Sized {
+  const REQUEST_TYPE: &'static str;
+  const XSD_NAME: &'static str;
+  const MESSAGE_TYPE: &'static str;
+  const DOCUMENT_VERSION: &'static str = "1.0";
+
+  fn into_envelope(self) -> RequestEnvelope<Self> {
+    RequestEnvelope::new(
+      Self::XSD_NAME,
+      &[("DocumentVersion", Self::DOCUMENT_VERSION)],
+      Self::MESSAGE_TYPE,
+      self,
+    )
+  }
+}
 
 /// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/inventory_update_feed/
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,16 +42,25 @@ pub struct InventoryUpdateFeedInventory {
 pub struct InventoryUpdateFeedItem {
   #[serde(rename = "SellerPartNumber")]
   pub seller_part_number: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "NeweggItemNumber")]
   pub newegg_item_number: Option<String>,
   #[serde(rename = "WarehouseLocation")]
   pub warehouse_location: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "FulfillmentOption")]
   pub fulfillment_option: Option<String>,
   #[serde(rename = "Inventory")]
   pub inventory: String,
 }
 
+impl FeedMessage for InventoryUpdateFeedMessage {
+  const REQUEST_TYPE: &'static str = "INVENTORY_DATA";
+  const XSD_NAME: &'static str = "BatchInventoryUpdate.xsd";
+  const MESSAGE_TYPE: &'static str = "Inventory";
+  const DOCUMENT_VERSION: &'static str = "2.0";
+}
+
 /// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/inventory_and_price_feed/
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InventoryAndPriceFeedMessage {
@@ -44,18 +78,839 @@ pub struct InventoryAndPriceFeedInventory {
 pub struct InventoryAndPriceFeedFeedItem {
   #[serde(rename = "SellerPartNumber")]
   pub seller_part_number: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "SellingPrice")]
   pub selling_price: Option<String>,
   #[serde(rename = "Inventory")]
   pub inventory: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "FulfillmentOption")]
   pub fulfillment_option: Option<String>,
   #[serde(rename = "Shipping")]
   pub shipping: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "ActivationMark")]
   pub activation_mark: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "Currency")]
   pub currency: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+}
+
+impl FeedMessage for InventoryAndPriceFeedMessage {
+  const REQUEST_TYPE: &'static str = "INVENTORY_AND_PRICE_DATA";
+  const XSD_NAME: &'static str = "Inventory.xsd";
+  const MESSAGE_TYPE: &'static str = "Inventory";
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/price_update_feed/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceUpdateFeedMessage {
+  #[serde(rename = "Price")]
+  pub price: PriceUpdateFeedPrice,
+}
+
+impl PriceUpdateFeedMessage {
+  pub fn new(items: Vec<PriceUpdateFeedItem>) -> Self {
+    PriceUpdateFeedMessage {
+      price: PriceUpdateFeedPrice { item: items },
+    }
+  }
+}
+
+impl FeedMessage for PriceUpdateFeedMessage {
+  const REQUEST_TYPE: &'static str = "PRICE_DATA";
+  const XSD_NAME: &'static str = "BatchPriceUpdate.xsd";
+  const MESSAGE_TYPE: &'static str = "Price";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceUpdateFeedPrice {
+  #[serde(rename = "Item")]
+  pub item: Vec<PriceUpdateFeedItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PriceUpdateFeedItem {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CountryCode")]
+  pub country_code: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Currency")]
+  pub currency: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MSRP", default, with = "crate::helpers::option_decimal_text")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MAP", default, with = "crate::helpers::option_decimal_text")]
+  pub map: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CheckoutMAP")]
+  #[serde(with = "crate::helpers::option_bool_string", default)]
+  pub checkout_map: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellingPrice", default, with = "crate::helpers::option_decimal_text")]
+  pub selling_price: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Shipping")]
+  pub shipping: Option<ShippingCharge>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ActivationMark")]
+  #[serde(with = "crate::helpers::option_bool_string", default)]
+  pub activation_mark: Option<bool>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/order_ship_notice_feed/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderShipNoticeFeedMessage {
+  #[serde(rename = "ShipNotice")]
+  pub ship_notice: ShipNotice,
+}
+
+impl OrderShipNoticeFeedMessage {
+  /// One notice package per package of every action.
+  pub fn from_actions(actions: &[ShipOrderAction]) -> Self {
+    let package = actions
+      .iter()
+      .flat_map(|action| {
+        action.packages().iter().map(move |package| ShipNoticePackage {
+          order_number: action.order_number().to_string(),
+          actual_shipping_carrier: package.ship_carrier().to_string(),
+          actual_shipping_method: package.ship_service().to_string(),
+          tracking_number: package.tracking_number().to_string(),
+          item_information: ShipNoticeItemInformation {
+            item: package
+              .items()
+              .iter()
+              .map(|item| ShipNoticeItem {
+                seller_part_number: item.seller_part_number().to_string(),
+                newegg_item_number: None,
+                shipped_quantity: item.shipped_qty().to_string(),
+              })
+              .collect(),
+          },
+        })
+      })
+      .collect();
+    OrderShipNoticeFeedMessage {
+      ship_notice: ShipNotice { package },
+    }
+  }
+
+  pub fn order_numbers(&self) -> Vec<i64> {
+    let mut numbers: Vec<i64> = vec![];
+    for package in &self.ship_notice.package {
+      if let Ok(number) = package.order_number.parse() {
+        if !numbers.contains(&number) {
+          numbers.push(number);
+        }
+      }
+    }
+    numbers
+  }
+}
+
+impl FeedMessage for OrderShipNoticeFeedMessage {
+  const REQUEST_TYPE: &'static str = "ORDER_SHIP_NOTICE_DATA";
+  const XSD_NAME: &'static str = "OrderShipNotice.xsd";
+  const MESSAGE_TYPE: &'static str = "OrderShipNotice";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShipNotice {
+  #[serde(rename = "Package")]
+  pub package: Vec<ShipNoticePackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShipNoticePackage {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: String,
+  #[serde(rename = "ActualShippingCarrier")]
+  pub actual_shipping_carrier: String,
+  #[serde(rename = "ActualShippingMethod")]
+  pub actual_shipping_method: String,
+  #[serde(rename = "TrackingNumber")]
+  pub tracking_number: String,
+  #[serde(rename = "ItemInformation")]
+  pub item_information: ShipNoticeItemInformation,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShipNoticeItemInformation {
+  #[serde(rename = "Item")]
+  pub item: Vec<ShipNoticeItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShipNoticeItem {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "NeweggItemNumber")]
   pub newegg_item_number: Option<String>,
+  #[serde(rename = "ShippedQuantity")]
+  pub shipped_quantity: String,
+}
+
+/// Bulk version of `OrderApi::cancel_order`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderCancellationFeedMessage {
+  #[serde(rename = "OrderCancellation")]
+  pub order_cancellation: OrderCancellation,
+}
+
+impl OrderCancellationFeedMessage {
+  pub fn new<I>(orders: I) -> Self
+  where
+    I: IntoIterator<Item = (i64, CancelOrderReasonCode)>,
+  {
+    OrderCancellationFeedMessage {
+      order_cancellation: OrderCancellation {
+        order: orders
+          .into_iter()
+          .map(|(order_number, cancel_reason)| OrderCancellationItem {
+            order_number: order_number.to_string(),
+            cancel_reason,
+          })
+          .collect(),
+      },
+    }
+  }
+
+  pub fn order_numbers(&self) -> Vec<i64> {
+    self
+      .order_cancellation
+      .order
+      .iter()
+      .filter_map(|order| order.order_number.parse().ok())
+      .collect()
+  }
+}
+
+impl FeedMessage for OrderCancellationFeedMessage {
+  const REQUEST_TYPE: &'static str = "ORDER_CANCELLATION_DATA";
+  const XSD_NAME: &'static str = "OrderCancellation.xsd";
+  const MESSAGE_TYPE: &'static str = "OrderCancellation";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderCancellation {
+  #[serde(rename = "Order")]
+  pub order: Vec<OrderCancellationItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderCancellationItem {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: String,
+  #[serde(rename = "CancelReason")]
+  pub cancel_reason: CancelOrderReasonCode,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/item_creation_feed/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemCreationFeedMessage {
+  #[serde(rename = "Itemfeed")]
+  pub item_feed: ItemFeed,
+}
+
+impl ItemCreationFeedMessage {
+  pub fn new(sub_category_id: &str, items: Vec<ItemCreationFeedItem>) -> Self {
+    ItemCreationFeedMessage {
+      item_feed: ItemFeed {
+        summary_info: ItemFeedSummaryInfo {
+          sub_category_id: sub_category_id.to_string(),
+        },
+        item: items,
+      },
+    }
+  }
+}
+
+impl FeedMessage for ItemCreationFeedMessage {
+  const REQUEST_TYPE: &'static str = "ITEM_DATA";
+  const XSD_NAME: &'static str = "BatchItemCreation.xsd";
+  const MESSAGE_TYPE: &'static str = "BatchItemCreation";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemFeed {
+  #[serde(rename = "SummaryInfo")]
+  pub summary_info: ItemFeedSummaryInfo,
+  #[serde(rename = "Item")]
+  pub item: Vec<ItemCreationFeedItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemFeedSummaryInfo {
+  #[serde(rename = "SubCategoryID")]
+  pub sub_category_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemCreationFeedItem {
+  #[serde(rename = "Action")]
+  pub action: ItemFeedAction,
+  #[serde(rename = "BasicInfo")]
+  pub basic_info: ItemBasicInfo,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SubCategoryProperty")]
+  pub sub_category_property: Option<SubCategoryProperty>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ItemFeedAction {
+  #[serde(rename = "Create Item")]
+  CreateItem,
+  #[serde(rename = "Update Item")]
+  UpdateItem,
+  #[serde(rename = "Update/Append Image")]
+  UpdateAppendImage,
+  #[serde(rename = "Replace Image")]
+  ReplaceImage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ItemBasicInfo {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "Manufacturer")]
+  pub manufacturer: String,
+  #[serde(rename = "ManufacturerPartNumberOrISBN")]
+  pub manufacturer_part_number_or_isbn: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "UPC")]
+  pub upc: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ManufacturerItemURL")]
+  pub manufacturer_item_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RelatedSellerPartNumber")]
+  pub related_seller_part_number: Option<String>,
+  #[serde(rename = "WebsiteShortTitle")]
+  pub website_short_title: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "BulletDescription")]
+  pub bullet_description: Option<String>,
+  #[serde(rename = "ProductDescription")]
+  pub product_description: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemDimension")]
+  pub item_dimension: Option<ItemDimension>,
+  #[serde(rename = "ItemWeight", with = "crate::helpers::decimal_text")]
+  pub item_weight: BigDecimal,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PacksOrSets")]
+  pub packs_or_sets: Option<i32>,
+  #[serde(rename = "ItemCondition")]
+  pub item_condition: ItemCondition,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemPackage")]
+  pub item_package: Option<ItemPackage>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShippingRestriction")]
+  pub shipping_restriction: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Currency")]
+  pub currency: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MSRP", default, with = "crate::helpers::option_decimal_text")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "MAP", default, with = "crate::helpers::option_decimal_text")]
+  pub map: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CheckoutMAP")]
+  #[serde(with = "crate::helpers::option_bool_string", default)]
+  pub checkout_map: Option<bool>,
+  #[serde(rename = "SellingPrice", with = "crate::helpers::decimal_text")]
+  pub selling_price: BigDecimal,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Shipping")]
+  pub shipping: Option<ShippingCharge>,
+  #[serde(rename = "Inventory")]
+  pub inventory: i32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ActivationMark")]
+  #[serde(with = "crate::helpers::option_bool_string", default)]
+  pub activation_mark: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemImages")]
+  pub item_images: Option<ItemImages>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Warning")]
+  pub warning: Option<ItemWarning>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ItemDimension {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemLength", default, with = "crate::helpers::option_decimal_text")]
+  pub item_length: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemWidth", default, with = "crate::helpers::option_decimal_text")]
+  pub item_width: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ItemHeight", default, with = "crate::helpers::option_decimal_text")]
+  pub item_height: Option<BigDecimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ItemCondition {
+  #[default]
+  New,
+  Refurbished,
+  #[serde(rename = "Used - Like New")]
+  UsedLikeNew,
+  #[serde(rename = "Used - Very Good")]
+  UsedVeryGood,
+  #[serde(rename = "Used - Good")]
+  UsedGood,
+  #[serde(rename = "Used - Acceptable")]
+  UsedAcceptable,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ItemPackage {
+  Retail,
+  #[serde(rename = "OEM")]
+  Oem,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ShippingCharge {
+  Default,
+  Free,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum YesNo {
+  Yes,
+  No,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ItemImages {
+  #[serde(rename = "Image")]
+  pub image: Vec<ItemImage>,
+}
+
+impl ItemImages {
+  /// The first URL becomes the primary image.
+  pub fn from_urls<I, S>(urls: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    ItemImages {
+      image: urls
+        .into_iter()
+        .enumerate()
+        .map(|(i, url)| ItemImage {
+          image_url: url.into(),
+          is_primary: Some(i == 0),
+        })
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemImage {
+  #[serde(rename = "ImageUrl")]
+  pub image_url: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "IsPrimary")]
+  #[serde(with = "crate::helpers::option_bool_string", default)]
+  pub is_primary: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ItemWarning {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Prop65")]
+  pub prop65: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Prop65Motherboard")]
+  pub prop65_motherboard: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CountryOfOrigin")]
+  pub country_of_origin: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OverAge18Verification")]
+  pub over_age18_verification: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ChokingHazard")]
+  pub choking_hazard: Option<ChokingHazard>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChokingHazard {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SmallParts")]
+  pub small_parts: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SmallBall")]
+  pub small_ball: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Balloons")]
+  pub balloons: Option<YesNo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Marble")]
+  pub marble: Option<YesNo>,
+}
+
+/// Subcategory specific properties, e.g. `<MonitorLCDs><Brand>...</Brand></MonitorLCDs>`.
+/// Names come from `SellerApi::get_subcategory_property_list`, repeated names
+/// give multi-value properties.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubCategoryProperty {
+  pub name: String,
+  pub properties: Vec<(String, String)>,
+}
+
+impl SubCategoryProperty {
+  pub fn new(name: &str) -> Self {
+    SubCategoryProperty {
+      name: name.to_string(),
+      properties: vec![],
+    }
+  }
+
+  pub fn property(mut self, name: &str, value: &str) -> Self {
+    self.properties.push((name.to_string(), value.to_string()));
+    self
+  }
+}
+
+impl Serialize for SubCategoryProperty {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry(&self.name, &PropertyValues(&self.properties))?;
+    map.end()
+  }
+}
+
+/// Properties in order, repeated names are grouped into a list at the
+/// position of their first value. XML writes the list as repeated elements.
+struct PropertyValues<'a>(&'a [(String, String)]);
+
+impl Serialize for PropertyValues<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut grouped: Vec<(&str, Vec<&str>)> = vec![];
+    for (name, value) in self.0 {
+      match grouped.iter_mut().find(|(n, _)| n == name) {
+        Some((_, values)) => values.push(value),
+        None => grouped.push((name, vec![value])),
+      }
+    }
+    let mut map = serializer.serialize_map(Some(grouped.len()))?;
+    for (name, values) in grouped {
+      if values.len() == 1 {
+        map.serialize_entry(name, values[0])?;
+      } else {
+        map.serialize_entry(name, &values)?;
+      }
+    }
+    map.end()
+  }
+}
+
+impl<'de> Deserialize<'de> for SubCategoryProperty {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct PropertyVisitor;
+
+    impl<'de> Visitor<'de> for PropertyVisitor {
+      type Value = SubCategoryProperty;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a subcategory element")
+      }
+
+      fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+      where
+        A: MapAccess<'de>,
+      {
+        let (name, values) = map
+          .next_entry::<String, Properties>()?
+          .ok_or_else(|| de::Error::custom("empty subcategory property"))?;
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(SubCategoryProperty {
+          name,
+          properties: values.0,
+        })
+      }
+    }
+
+    deserializer.deserialize_map(PropertyVisitor)
+  }
+}
+
+/// Properties of a subcategory in document order.
+struct Properties(Vec<(String, String)>);
+
+impl<'de> Deserialize<'de> for Properties {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct PropertiesVisitor;
+
+    impl<'de> Visitor<'de> for PropertiesVisitor {
+      type Value = Properties;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("subcategory properties")
+      }
+
+      fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+      where
+        A: MapAccess<'de>,
+      {
+        let mut properties = vec![];
+        while let Some((name, values)) = map.next_entry::<String, PropertyText>()? {
+          for value in values.0 {
+            properties.push((name.clone(), value));
+          }
+        }
+        Ok(Properties(properties))
+      }
+    }
+
+    deserializer.deserialize_map(PropertiesVisitor)
+  }
+}
+
+/// A property value: a string or a list of strings in JSON, an element
+/// (read as a map holding its `$text`) in XML.
+struct PropertyText(Vec<String>);
+
+impl<'de> Deserialize<'de> for PropertyText {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct TextVisitor;
+
+    impl<'de> Visitor<'de> for TextVisitor {
+      type Value = PropertyText;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string or a list of strings")
+      }
+
+      fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(PropertyText(vec![v.to_string()]))
+      }
+
+      fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(PropertyText(vec![String::new()]))
+      }
+
+      fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+      where
+        A: SeqAccess<'de>,
+      {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element::<Option<String>>()? {
+          values.push(value.unwrap_or_default());
+        }
+        Ok(PropertyText(values))
+      }
+
+      fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+      where
+        A: MapAccess<'de>,
+      {
+        let mut text = String::new();
+        while let Some((key, value)) = map.next_entry::<String, String>()? {
+          if key == "$text" {
+            text = value;
+          }
+        }
+        Ok(PropertyText(vec![text]))
+      }
+    }
+
+    deserializer.deserialize_any(TextVisitor)
+  }
+}
+
+#[test]
+fn test_item_creation_feed_to_xml() {
+  use super::RequestEnvelope;
+
+  let item = ItemCreationFeedItem {
+    action: ItemFeedAction::CreateItem,
+    basic_info: ItemBasicInfo {
+      seller_part_number: "edifier-r1280t".to_string(),
+      manufacturer: "Edifier".to_string(),
+      manufacturer_part_number_or_isbn: "R1280T".to_string(),
+      website_short_title: "Edifier R1280T Powered Bookshelf Speakers".to_string(),
+      product_description: "Powered bookshelf speakers".to_string(),
+      item_weight: "10.5".parse().unwrap(),
+      selling_price: "99.99".parse().unwrap(),
+      inventory: 5,
+      activation_mark: Some(true),
+      item_images: Some(ItemImages::from_urls(vec!["https://a/1.jpg", "https://a/2.jpg"])),
+      ..Default::default()
+    },
+    sub_category_property: Some(
+      SubCategoryProperty::new("Speakers")
+        .property("SpeakerBrand", "Edifier")
+        .property("SpeakerFeatures", "Bluetooth")
+        .property("SpeakerFeatures", "Remote"),
+    ),
+  };
+  let request = RequestEnvelope::new(
+    "BatchItemCreation.xsd",
+    &[("DocumentVersion", "1.0")],
+    "BatchItemCreation",
+    ItemCreationFeedMessage::new("1045", vec![item]),
+  );
+  let doc = request.to_xml().unwrap();
+  assert!(doc.contains("<Action>Create Item</Action>"));
+  assert!(doc.contains("<ActivationMark>True</ActivationMark>"));
+  assert!(doc.contains("<SpeakerFeatures>Remote</SpeakerFeatures>"));
+
+  let parsed = RequestEnvelope::<ItemCreationFeedMessage>::from_xml(&doc).unwrap();
+  let item = &parsed.message.item_feed.item[0];
+  assert_eq!(item.basic_info.inventory, 5);
+  assert_eq!(item.basic_info.selling_price, "99.99".parse().unwrap());
+  assert_eq!(item.basic_info.item_images.as_ref().unwrap().image[1].is_primary, Some(false));
+  let property = item.sub_category_property.as_ref().unwrap();
+  assert_eq!(property.name, "Speakers");
+  assert_eq!(
+    property.properties[2],
+    ("SpeakerFeatures".to_string(), "Remote".to_string())
+  );
+}
+
+#[test]
+fn test_item_creation_feed_json() {
+  let item = ItemCreationFeedItem {
+    action: ItemFeedAction::UpdateItem,
+    basic_info: ItemBasicInfo {
+      seller_part_number: "edifier-r1280t".to_string(),
+      item_weight: "10.5".parse().unwrap(),
+      selling_price: "99.90".parse().unwrap(),
+      item_images: Some(ItemImages::from_urls(vec!["https://a/1.jpg"])),
+      ..Default::default()
+    },
+    sub_category_property: Some(
+      SubCategoryProperty::new("Speakers")
+        .property("SpeakerType", "Bookshelf")
+        .property("SpeakerFeatures", "Bluetooth")
+        .property("SpeakerBrand", "Edifier")
+        .property("SpeakerFeatures", "Remote"),
+    ),
+  };
+  let json = serde_json::to_string(&ItemCreationFeedMessage::new("1045", vec![item])).unwrap();
+  // the REST endpoint rejects nulls for optional elements
+  assert!(!json.contains("null"));
+  assert!(!json.contains("UPC"));
+  assert!(json.contains(r#""SellingPrice":"99.90""#));
+  assert!(json.contains(r#""IsPrimary":"True""#));
+  assert!(json.contains(
+    r#"{"Speakers":{"SpeakerType":"Bookshelf","SpeakerFeatures":["Bluetooth","Remote"],"SpeakerBrand":"Edifier"}}"#
+  ));
+
+  let parsed: ItemCreationFeedMessage = serde_json::from_str(&json).unwrap();
+  let item = &parsed.item_feed.item[0];
+  assert_eq!(item.basic_info.item_images.as_ref().unwrap().image[0].is_primary, Some(true));
+  let property = item.sub_category_property.as_ref().unwrap();
+  assert_eq!(property.properties[0].0, "SpeakerType");
+  assert_eq!(property.properties[2].1, "Remote");
+}
+
+#[test]
+fn test_price_update_feed_message() {
+  let message = PriceUpdateFeedMessage::new(vec![PriceUpdateFeedItem {
+    seller_part_number: "edifier-r1280t".to_string(),
+    selling_price: Some("99.90".parse().unwrap()),
+    checkout_map: Some(false),
+    shipping: Some(ShippingCharge::Free),
+    ..Default::default()
+  }]);
+  let doc = message.into_envelope().to_xml().unwrap();
+  assert!(doc.contains(r#"xsi:noNamespaceSchemaLocation="BatchPriceUpdate.xsd""#));
+  assert!(doc.contains("<MessageType>Price</MessageType>"));
+  assert!(doc.contains("<SellingPrice>99.90</SellingPrice>"));
+  assert!(doc.contains("<CheckoutMAP>False</CheckoutMAP>"));
+  assert!(!doc.contains("<MSRP>"));
+}
+
+#[test]
+fn test_order_ship_notice_feed() {
+  use super::ProcessingReportMessage;
+  use crate::order::{Package, ShipCarrier};
+
+  let actions: Vec<ShipOrderAction> = vec![101, 102]
+    .into_iter()
+    .map(|order_number| {
+      ShipOrderAction::new("A001", order_number)
+        .add_package(
+          Package::new(ShipCarrier::Ups, "Ground", &format!("1Z{}", order_number))
+            .add_item("edifier-r1280t", 1)
+            .finalize()
+            .unwrap(),
+        )
+        .finalize()
+    })
+    .collect();
+  let message = OrderShipNoticeFeedMessage::from_actions(&actions);
+  assert_eq!(message.order_numbers(), vec![101, 102]);
+  let doc = message.into_envelope().to_xml().unwrap();
+  assert!(doc.contains("<ActualShippingCarrier>UPS</ActualShippingCarrier>"));
+  assert!(doc.contains("<TrackingNumber>1Z102</TrackingNumber>"));
+
+  let report = ProcessingReportMessage::from_xml(
+    r#"<ProcessingReport>
+      <OriginalMessageName>OrderShipNotice.xml</OriginalMessageName>
+      <StatusCode>ProcessReport</StatusCode>
+      <ProcessingSummary>
+        <ProcessedCount>2</ProcessedCount>
+        <SuccessCount>1</SuccessCount>
+        <WithErrorCount>1</WithErrorCount>
+      </ProcessingSummary>
+      <Result>
+        <AdditionalInfo>
+          <OrderNumber>102</OrderNumber>
+        </AdditionalInfo>
+        <ErrorList>
+          <ErrorDescription>SO0001:Order has already been shipped</ErrorDescription>
+        </ErrorList>
+      </Result>
+    </ProcessingReport>"#,
+  )
+  .unwrap();
+  let results = report.processing_report.order_results(&[101, 102]);
+  assert!(results[0].is_success());
+  assert_eq!(results[1].status, super::OrderFeedStatus::Failed);
+  assert_eq!(results[1].errors, vec!["SO0001:Order has already been shipped"]);
+}
+
+#[test]
+fn test_order_cancellation_feed() {
+  let message = OrderCancellationFeedMessage::new(vec![(101, CancelOrderReasonCode::OutOfStock)]);
+  assert_eq!(message.order_numbers(), vec![101]);
+  let doc = message.into_envelope().to_xml().unwrap();
+  assert!(doc.contains("<CancelReason>24</CancelReason>"));
 }
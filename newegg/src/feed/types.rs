@@ -1,36 +1,65 @@
+use super::xml;
+use crate::result::{NeweggError, NeweggResult};
 use crate::types::MaybeList;
+use serde::de::DeserializeOwned;
+use serde::Serialize as SerializeT;
 use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+pub use crate::poll::{PollOptions, RequestStatus};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RequestEnvelope<T> {
   #[serde(rename = "-xmlns:xsi")]
-  pub xmlns_xsi: &'static str,
+  pub xmlns_xsi: String,
   #[serde(rename = "-xsi:noNamespaceSchemaLocation")]
-  pub xsd_name: &'static str,
-  #[serde(rename = "Header")]
-  pub header: HashMap<String, String>,
+  pub xsd_name: String,
+  /// In document order.
+  #[serde(rename = "Header", with = "header")]
+  pub header: Vec<(String, String)>,
   #[serde(rename = "MessageType")]
   pub message_type: String,
-  #[serde(rename = "Message")]
-  pub message: T,
   /// https://developer.newegg.com/newegg_marketplace_api/datafeed_management/submit_feed/inventory_and_price_feed/
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "Overwrite")]
   pub inventory_and_price_data_overwrite: Option<String>,
+  #[serde(rename = "Message")]
+  pub message: T,
+}
+
+/// `RequestEnvelope` with XML attribute names, the JSON names are prefixed
+/// with `-` instead of `@`.
+#[derive(Serialize, Deserialize)]
+struct XmlRequestEnvelope<'a, M> {
+  #[serde(rename = "@xmlns:xsi")]
+  xmlns_xsi: Cow<'a, str>,
+  // quick-xml strips the namespace prefix when reading
+  #[serde(rename = "@xsi:noNamespaceSchemaLocation", alias = "@noNamespaceSchemaLocation")]
+  xsd_name: Cow<'a, str>,
+  #[serde(rename = "Header", with = "header")]
+  header: Cow<'a, [(String, String)]>,
+  #[serde(rename = "MessageType")]
+  message_type: Cow<'a, str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Overwrite")]
+  overwrite: Option<Cow<'a, str>>,
+  #[serde(rename = "Message")]
+  message: M,
 }
 
 impl<T> RequestEnvelope<T> {
   pub fn new(
-    xsd_name: &'static str,
+    xsd_name: &str,
     headers: &[(&str, &str)],
     message_type: &str,
     message: T,
   ) -> Self {
     RequestEnvelope {
-      xmlns_xsi: "http://www.w3.org/2001/XMLSchema-instance",
-      xsd_name,
+      xmlns_xsi: "http://www.w3.org/2001/XMLSchema-instance".to_string(),
+      xsd_name: xsd_name.to_string(),
       header: headers
-        .into_iter()
+        .iter()
         .map(|(k, v)| (k.to_string(), v.to_string()))
         .collect(),
       message_type: message_type.to_string(),
@@ -51,6 +80,88 @@ impl<T> RequestEnvelope<T> {
   }
 }
 
+impl<T> RequestEnvelope<T>
+where
+  T: SerializeT,
+{
+  pub fn to_xml(&self) -> NeweggResult<String> {
+    let envelope = XmlRequestEnvelope {
+      xmlns_xsi: Cow::Borrowed(&self.xmlns_xsi),
+      xsd_name: Cow::Borrowed(&self.xsd_name),
+      header: Cow::Borrowed(&self.header),
+      message_type: Cow::Borrowed(&self.message_type),
+      overwrite: self.inventory_and_price_data_overwrite.as_deref().map(Cow::Borrowed),
+      message: &self.message,
+    };
+    xml::to_string("NeweggEnvelope", &envelope)
+  }
+}
+
+impl<T> RequestEnvelope<T>
+where
+  T: DeserializeOwned,
+{
+  pub fn from_xml(xml: &str) -> NeweggResult<Self> {
+    let envelope: XmlRequestEnvelope<T> = xml::from_str(xml)?;
+    Ok(RequestEnvelope {
+      xmlns_xsi: envelope.xmlns_xsi.into_owned(),
+      xsd_name: envelope.xsd_name.into_owned(),
+      header: envelope.header.into_owned(),
+      message_type: envelope.message_type.into_owned(),
+      inventory_and_price_data_overwrite: envelope.overwrite.map(Cow::into_owned),
+      message: envelope.message,
+    })
+  }
+}
+
+/// Envelope headers as an element per name, kept in order.
+mod header {
+  use serde::de::{MapAccess, Visitor};
+  use serde::ser::SerializeMap;
+  use serde::{Deserializer, Serializer};
+  use std::fmt;
+
+  pub fn serialize<S>(header: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut map = serializer.serialize_map(Some(header.len()))?;
+    for (name, value) in header {
+      map.serialize_entry(name, value)?;
+    }
+    map.end()
+  }
+
+  pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+  where
+    D: Deserializer<'de>,
+    T: From<Vec<(String, String)>>,
+  {
+    struct HeaderVisitor;
+
+    impl<'de> Visitor<'de> for HeaderVisitor {
+      type Value = Vec<(String, String)>;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an envelope header")
+      }
+
+      fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+      where
+        A: MapAccess<'de>,
+      {
+        let mut header = vec![];
+        while let Some(entry) = map.next_entry()? {
+          header.push(entry);
+        }
+        Ok(header)
+      }
+    }
+
+    deserializer.deserialize_map(HeaderVisitor).map(T::from)
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResponseEnvelope<T> {
   #[serde(rename = "NeweggEnvelope")]
@@ -59,14 +170,34 @@ pub struct ResponseEnvelope<T> {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResponseEnvelopeInner<T> {
-  #[serde(rename = "Header")]
-  pub header: HashMap<String, String>,
+  #[serde(rename = "Header", with = "header")]
+  pub header: Vec<(String, String)>,
   #[serde(rename = "MessageType")]
   pub message_type: String,
   #[serde(rename = "Message")]
   pub message: T,
 }
 
+impl<T> ResponseEnvelope<T>
+where
+  T: SerializeT,
+{
+  pub fn to_xml(&self) -> NeweggResult<String> {
+    xml::to_string("NeweggEnvelope", &self.inner)
+  }
+}
+
+impl<T> ResponseEnvelope<T>
+where
+  T: DeserializeOwned,
+{
+  pub fn from_xml(xml: &str) -> NeweggResult<Self> {
+    Ok(ResponseEnvelope {
+      inner: xml::from_str(xml)?,
+    })
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FeedResponse {
   #[serde(rename = "IsSuccess")]
@@ -97,13 +228,11 @@ pub struct FeedResponseList {
   pub request_type: String,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RequestStatus {
-  Submitted,
-  InProgress,
-  Finished,
-  Cancelled,
+impl FeedResponseList {
+  /// Fails on a status this crate does not know.
+  pub fn status(&self) -> NeweggResult<RequestStatus> {
+    self.request_status.parse()
+  }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -118,6 +247,17 @@ pub struct GetRequestStatus {
   pub request_status: Option<RequestStatus>,
 }
 
+impl GetRequestStatus {
+  pub fn new(request_ids: &[&str]) -> Self {
+    GetRequestStatus {
+      request_id_list: RequestIdList {
+        request_id: request_ids.iter().map(ToString::to_string).collect(),
+      },
+      ..Default::default()
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct RequestIdList {
   #[serde(rename = "RequestID")]
@@ -130,7 +270,88 @@ pub struct ProcessingReportMessage {
   pub processing_report: ProcessingReport,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl ProcessingReportMessage {
+  /// Archives the report as a `ProcessingReport` XML document.
+  pub fn to_xml(&self) -> NeweggResult<String> {
+    xml::to_string("ProcessingReport", &self.processing_report)
+  }
+
+  /// Reads a `ProcessingReport` document, or a `NeweggEnvelope` carrying one
+  /// as found in the seller FTP outbound folders.
+  pub fn from_xml(xml: &str) -> NeweggResult<Self> {
+    let report: XmlProcessingReport = match xml::root_name(xml)?.as_str() {
+      "NeweggEnvelope" => {
+        xml::from_str::<ResponseEnvelopeInner<XmlProcessingReportMessage>>(xml)?
+          .message
+          .processing_report
+      }
+      "ProcessingReport" => xml::from_str(xml)?,
+      root => return Err(NeweggError::Xml(format!("unexpected root element: {}", root))),
+    };
+    Ok(ProcessingReportMessage {
+      processing_report: report.into(),
+    })
+  }
+}
+
+// XML can not tell a single element from a list, so processing reports are
+// read with `Vec`s instead of `MaybeList`s.
+
+#[derive(Deserialize)]
+struct XmlProcessingReportMessage {
+  #[serde(rename = "ProcessingReport")]
+  processing_report: XmlProcessingReport,
+}
+
+#[derive(Deserialize)]
+struct XmlProcessingReport {
+  #[serde(rename = "OriginalMessageName")]
+  original_message_name: String,
+  #[serde(rename = "StatusCode")]
+  status_code: String,
+  #[serde(rename = "ProcessingSummary")]
+  processing_summary: ProcessingSummary,
+  #[serde(rename = "Result", default)]
+  result: Vec<XmlProcessingResult>,
+}
+
+#[derive(Deserialize)]
+struct XmlProcessingResult {
+  #[serde(rename = "AdditionalInfo")]
+  additional_info: AdditionalInfo,
+  #[serde(rename = "ErrorList", default)]
+  error_list: XmlErrorList,
+}
+
+#[derive(Deserialize, Default)]
+struct XmlErrorList {
+  #[serde(rename = "ErrorDescription", default)]
+  error_description: Vec<String>,
+}
+
+impl From<XmlProcessingReport> for ProcessingReport {
+  fn from(report: XmlProcessingReport) -> Self {
+    ProcessingReport {
+      original_message_name: report.original_message_name,
+      status_code: report.status_code,
+      processing_summary: report.processing_summary,
+      result: MaybeList::List(
+        report
+          .result
+          .into_iter()
+          .map(|result| ProcessingResult {
+            additional_info: result.additional_info,
+            error_list: ErrorList {
+              error_description: MaybeList::List(result.error_list.error_description),
+            },
+          })
+          .collect(),
+      ),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessingReport {
   #[serde(rename = "OriginalMessageName")]
   pub original_message_name: String,
@@ -138,11 +359,11 @@ pub struct ProcessingReport {
   pub status_code: String,
   #[serde(rename = "ProcessingSummary")]
   pub processing_summary: ProcessingSummary,
-  #[serde(rename = "Result")]
+  #[serde(rename = "Result", default)]
   pub result: MaybeList<ProcessingResult>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessingSummary {
   #[serde(rename = "ProcessedCount")]
   pub processed_count: String,
@@ -152,32 +373,136 @@ pub struct ProcessingSummary {
   pub with_error_count: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessingResult {
   #[serde(rename = "AdditionalInfo")]
   pub additional_info: AdditionalInfo,
-  #[serde(rename = "ErrorList")]
+  #[serde(rename = "ErrorList", default)]
   pub error_list: ErrorList,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdditionalInfo {
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "SubCategoryID")]
   pub sub_category_id: Option<String>,
-  #[serde(rename = "SellerPartNumber")]
+  #[serde(rename = "SellerPartNumber", default)]
   pub seller_part_number: String,
+  /// Set for order ship notice and cancellation feeds
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderNumber")]
+  pub order_number: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "ManufacturerPartNumberOrISBN")]
   pub manufacturer_part_number_or_isbn: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
   #[serde(rename = "UPC")]
   pub upc: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ErrorList {
   #[serde(rename = "ErrorDescription")]
   pub error_description: MaybeList<String>,
 }
 
+impl ProcessingReport {
+  /// Error descriptions of every result, keyed by `SellerPartNumber`.
+  pub fn errors_by_seller_part_number(&self) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for result in self.result.as_slice() {
+      let errors = result.error_list.error_description.as_slice();
+      if errors.is_empty() || result.additional_info.seller_part_number.is_empty() {
+        continue;
+      }
+      map
+        .entry(result.additional_info.seller_part_number.clone())
+        .or_default()
+        .extend(errors.iter().cloned());
+    }
+    map
+  }
+}
+
+/// Outcome of one order in an order ship notice or cancellation feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFeedResult {
+  pub order_number: i64,
+  pub status: OrderFeedStatus,
+  pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderFeedStatus {
+  Success,
+  Failed,
+  /// Not listed in the report, and the processing summary does not account
+  /// for it as a success.
+  Unknown,
+}
+
+impl OrderFeedResult {
+  pub fn is_success(&self) -> bool {
+    self.status == OrderFeedStatus::Success
+  }
+}
+
+impl ProcessingReport {
+  /// Maps the report back to the submitted orders. Newegg only lists orders
+  /// with errors, so orders missing from the report succeeded, as long as
+  /// the processing summary counts enough successes and every error result
+  /// names one of `order_numbers`. Otherwise they are `Unknown`.
+  pub fn order_results(&self, order_numbers: &[i64]) -> Vec<OrderFeedResult> {
+    let result_order_number = |result: &ProcessingResult| {
+      result
+        .additional_info
+        .order_number
+        .as_ref()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+    };
+    let mut results: Vec<OrderFeedResult> = order_numbers
+      .iter()
+      .map(|&order_number| {
+        let errors: Vec<String> = self
+          .result
+          .as_slice()
+          .iter()
+          .filter(|result| result_order_number(result) == Some(order_number))
+          .flat_map(|result| result.error_list.error_description.as_slice().iter().cloned())
+          .collect();
+        OrderFeedResult {
+          order_number,
+          status: if errors.is_empty() {
+            OrderFeedStatus::Success
+          } else {
+            OrderFeedStatus::Failed
+          },
+          errors,
+        }
+      })
+      .collect();
+
+    let unmatched = results.iter().filter(|result| result.is_success()).count();
+    let unattributed_errors = self.result.as_slice().iter().any(|result| {
+      !result.error_list.error_description.as_slice().is_empty()
+        && !result_order_number(result)
+          .map(|number| order_numbers.contains(&number))
+          .unwrap_or(false)
+    });
+    let success_count = self.processing_summary.success_count.trim().parse::<usize>().ok();
+    let accounted = match success_count {
+      Some(count) => count >= unmatched && !unattributed_errors,
+      None => false,
+    };
+    if !accounted {
+      for result in results.iter_mut().filter(|result| result.is_success()) {
+        result.status = OrderFeedStatus::Unknown;
+      }
+    }
+    results
+  }
+}
+
 #[test]
 fn test_deserialize_processing_report_message() {
   let json = r#"{
@@ -202,5 +527,82 @@ fn test_deserialize_processing_report_message() {
       "StatusCode": "ProcessReport"
     }
   }"#;
-  serde_json::from_str::<ProcessingReportMessage>(json).unwrap();
+  let message = serde_json::from_str::<ProcessingReportMessage>(json).unwrap();
+  let errors = message.processing_report.errors_by_seller_part_number();
+  assert_eq!(
+    errors["edifier-r1280t-fba"],
+    vec!["CEI0001:Can't find this item in the system, please create it first"]
+  );
+}
+
+#[test]
+fn test_request_envelope_header_order() {
+  let request = RequestEnvelope::new(
+    "Inventory.xsd",
+    &[("DocumentVersion", "1.0"), ("AccountID", "A001")],
+    "Inventory",
+    (),
+  );
+  let json = serde_json::to_string(&request).unwrap();
+  assert!(json.contains(r#""Header":{"DocumentVersion":"1.0","AccountID":"A001"}"#));
+  assert!(!json.contains("Overwrite"));
+
+  let xml = request.to_xml().unwrap();
+  assert!(xml.contains("<DocumentVersion>1.0</DocumentVersion>\n    <AccountID>A001</AccountID>"));
+}
+
+#[test]
+fn test_order_results_unmatched() {
+  let report = |success_count: &str, results: Vec<(&str, &str)>| ProcessingReport {
+    original_message_name: "OrderShipNotice.xml".to_string(),
+    status_code: "ProcessReport".to_string(),
+    processing_summary: ProcessingSummary {
+      processed_count: "3".to_string(),
+      success_count: success_count.to_string(),
+      with_error_count: results.len().to_string(),
+    },
+    result: MaybeList::List(
+      results
+        .into_iter()
+        .map(|(order_number, error)| ProcessingResult {
+          additional_info: AdditionalInfo {
+            sub_category_id: None,
+            seller_part_number: String::new(),
+            order_number: Some(order_number.to_string()),
+            manufacturer_part_number_or_isbn: None,
+            upc: None,
+          },
+          error_list: ErrorList {
+            error_description: MaybeList::Single(error.to_string()),
+          },
+        })
+        .collect(),
+    ),
+  };
+  let statuses = |report: ProcessingReport| -> Vec<OrderFeedStatus> {
+    report
+      .order_results(&[101, 102, 103])
+      .into_iter()
+      .map(|result| result.status)
+      .collect()
+  };
+
+  assert_eq!(
+    statuses(report("2", vec![("102", "SO0001:Order has already been shipped")])),
+    vec![OrderFeedStatus::Success, OrderFeedStatus::Failed, OrderFeedStatus::Success]
+  );
+  // the summary only counts one success for the two unlisted orders
+  assert_eq!(
+    statuses(report("1", vec![("102", "SO0001:Order has already been shipped")])),
+    vec![OrderFeedStatus::Unknown, OrderFeedStatus::Failed, OrderFeedStatus::Unknown]
+  );
+  // an error for an order that was not submitted may belong to any of them
+  assert_eq!(
+    statuses(report("2", vec![("999", "SO0002:Order not found")])),
+    vec![OrderFeedStatus::Unknown; 3]
+  );
+  assert_eq!(
+    statuses(report("", vec![])),
+    vec![OrderFeedStatus::Unknown; 3]
+  );
 }
@@ -0,0 +1,155 @@
+//! XML encoding of feed envelopes, built on quick-xml's serde support.
+//!
+//! Field names starting with `@` are attributes and `Vec`s are repeated
+//! elements. `None`s have to be skipped with `skip_serializing_if`, otherwise
+//! they are written as empty elements.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::result::{NeweggError, NeweggResult};
+
+const DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// Serializes `value` as an XML document with a `root` element.
+pub fn to_string<T>(root: &str, value: &T) -> NeweggResult<String>
+where
+  T: Serialize + ?Sized,
+{
+  let mut xml = format!("{}\n", DECLARATION);
+  let mut serializer = quick_xml::se::Serializer::with_root(&mut xml, Some(root))?;
+  serializer.indent(' ', 2);
+  value.serialize(serializer)?;
+  Ok(xml)
+}
+
+/// Deserializes the root element of an XML document.
+pub fn from_str<T>(xml: &str) -> NeweggResult<T>
+where
+  T: DeserializeOwned,
+{
+  Ok(quick_xml::de::from_str(xml)?)
+}
+
+/// Name of the root element of an XML document.
+pub fn root_name(xml: &str) -> NeweggResult<String> {
+  let mut reader = Reader::from_str(xml);
+  loop {
+    match reader.read_event()? {
+      Event::Start(e) | Event::Empty(e) => {
+        return Ok(String::from_utf8_lossy(e.name().as_ref()).into_owned())
+      }
+      Event::Eof => return Err(NeweggError::Xml("no root element".to_string())),
+      _ => {}
+    }
+  }
+}
+
+#[test]
+fn test_request_envelope_to_xml() {
+  use super::message::*;
+  use super::RequestEnvelope;
+
+  let request = RequestEnvelope::new(
+    "Inventory.xsd",
+    &[("DocumentVersion", "1.0")],
+    "Inventory",
+    InventoryUpdateFeedMessage {
+      inventory: InventoryUpdateFeedInventory {
+        item: vec![InventoryUpdateFeedItem {
+          seller_part_number: "A&B".to_string(),
+          warehouse_location: "USA".to_string(),
+          inventory: "10".to_string(),
+          ..Default::default()
+        }],
+      },
+    },
+  );
+  let xml = request.to_xml().unwrap();
+  assert_eq!(
+    xml,
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<NeweggEnvelope xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="Inventory.xsd">
+  <Header>
+    <DocumentVersion>1.0</DocumentVersion>
+  </Header>
+  <MessageType>Inventory</MessageType>
+  <Message>
+    <Inventory>
+      <Item>
+        <SellerPartNumber>A&amp;B</SellerPartNumber>
+        <WarehouseLocation>USA</WarehouseLocation>
+        <Inventory>10</Inventory>
+      </Item>
+    </Inventory>
+  </Message>
+</NeweggEnvelope>"#
+  );
+
+  let parsed = RequestEnvelope::<InventoryUpdateFeedMessage>::from_xml(&xml).unwrap();
+  assert_eq!(parsed.xsd_name, "Inventory.xsd");
+  assert_eq!(parsed.message.inventory.item.len(), 1);
+  assert_eq!(parsed.message.inventory.item[0].seller_part_number, "A&B");
+  assert_eq!(parsed.message.inventory.item[0].newegg_item_number, None);
+}
+
+#[test]
+fn test_processing_report_from_xml() {
+  use super::ProcessingReportMessage;
+
+  let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<NeweggEnvelope xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="ProcessingReport.xsd">
+  <Header>
+    <DocumentVersion>1.0</DocumentVersion>
+  </Header>
+  <MessageType>ProcessingReport</MessageType>
+  <Message>
+    <ProcessingReport>
+      <OriginalMessageName>Inventory_20200502012000_0001.xml</OriginalMessageName>
+      <OriginalMessageType>Inventory</OriginalMessageType>
+      <StatusCode>ProcessReport</StatusCode>
+      <ProcessingSummary>
+        <ProcessedCount>3</ProcessedCount>
+        <SuccessCount>1</SuccessCount>
+        <WithErrorCount>2</WithErrorCount>
+      </ProcessingSummary>
+      <Result>
+        <AdditionalInfo>
+          <SellerPartNumber>edifier-r1280t-fba</SellerPartNumber>
+        </AdditionalInfo>
+        <ErrorList>
+          <ErrorDescription><![CDATA[CEI0001:Can't find this item in the system, please create it first]]></ErrorDescription>
+        </ErrorList>
+      </Result>
+      <Result>
+        <AdditionalInfo>
+          <SellerPartNumber>edifier-s3000</SellerPartNumber>
+        </AdditionalInfo>
+        <ErrorList>
+          <ErrorDescription>CEI0002:first</ErrorDescription>
+          <ErrorDescription>CEI0003:second</ErrorDescription>
+        </ErrorList>
+      </Result>
+    </ProcessingReport>
+  </Message>
+</NeweggEnvelope>"#;
+  let message = ProcessingReportMessage::from_xml(xml).unwrap();
+  let report = &message.processing_report;
+  assert_eq!(report.processing_summary.with_error_count, "2");
+  assert_eq!(
+    report.result.as_slice()[0].error_list.error_description.as_slice()[0],
+    "CEI0001:Can't find this item in the system, please create it first"
+  );
+  assert_eq!(
+    report.result.as_slice()[1].error_list.error_description.as_slice(),
+    ["CEI0002:first", "CEI0003:second"]
+  );
+
+  let archived = message.to_xml().unwrap();
+  assert!(!archived.contains("<UPC"));
+  let message = ProcessingReportMessage::from_xml(&archived).unwrap();
+  assert_eq!(message.processing_report.status_code, "ProcessReport");
+  assert_eq!(message.processing_report.result.as_slice().len(), 2);
+}
@@ -1,10 +1,9 @@
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use chrono_tz::{Tz, US::Pacific};
 use serde::de::{self, Deserialize, Deserializer, Visitor};
 use serde::ser::{Serialize, Serializer};
 use std::fmt;
 
-use crate::result::{NeweggResult, NeweggError};
 
 /// Newegg Marketplace API requires the datetime field to be in Pacific Standard Time
 /// in all request and response content. Please ensure in all your files and requests,
@@ -12,7 +11,8 @@ use crate::result::{NeweggResult, NeweggError};
 #[derive(Debug, Clone, Copy)]
 pub struct NeweggDateTime(DateTime<Tz>);
 
-const FORMAT: &'static str = "%m/%d/%Y %H:%M:%S";
+const FORMAT: &str = "%m/%d/%Y %H:%M:%S";
+const DATE_FORMAT: &str = "%m/%d/%Y";
 
 impl NeweggDateTime {
   pub fn as_utc(&self) -> DateTime<Utc> {
@@ -46,9 +46,20 @@ impl<'de> Visitor<'de> for NeweggDateTimeVisitor {
   where
     E: de::Error,
   {
-    let pdt = Pacific
-      .datetime_from_str(s, FORMAT)
+    // settlement reports only carry the date, e.g. '2/22/2013'
+    let pdt = NaiveDateTime::parse_from_str(s, FORMAT)
+      .or_else(|err| {
+        NaiveDate::parse_from_str(s, DATE_FORMAT)
+          .map(|date| date.and_time(NaiveTime::MIN))
+          .map_err(|_| err)
+      })
       .map_err(|err| E::custom(format!("parse date error: {}", err)))?;
+    // Same as the deprecated `TimeZone::datetime_from_str`: ambiguous or
+    // skipped local times (DST transitions) are errors.
+    let pdt = Pacific
+      .from_local_datetime(&pdt)
+      .single()
+      .ok_or_else(|| E::custom(format!("ambiguous or invalid local date: {}", s)))?;
     Ok(NeweggDateTime(pdt))
   }
 }
@@ -123,10 +134,201 @@ macro_rules! enum_number {
   }
 }
 
-pub async fn block<F, R>(f: F) -> NeweggResult<R>
+/// Money as a JSON number, read from its decimal text instead of a binary
+/// float, e.g. `#[serde(with = "crate::helpers::decimal")]`.
+pub(crate) mod decimal {
+  use bigdecimal::BigDecimal;
+  use serde::de::{self, Deserializer, Visitor};
+  use serde::ser::{self, Serializer};
+  use std::fmt;
+  use std::str::FromStr;
+
+  pub fn serialize<S>(v: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    // the shortest float that parses back to the same value, `19.99` stays `19.99`
+    let v = f64::from_str(&v.to_string()).map_err(ser::Error::custom)?;
+    serializer.serialize_f64(v)
+  }
+
+  pub fn deserialize<'de, D>(deserializer: D) -> Result<BigDecimal, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_any(DecimalVisitor)
+  }
+
+  struct DecimalVisitor;
+
+  impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = BigDecimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+      formatter.write_str("a decimal number or string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<BigDecimal, E> {
+      BigDecimal::from_str(v.trim()).map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<BigDecimal, E> {
+      self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<BigDecimal, E> {
+      Ok(BigDecimal::from(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<BigDecimal, E> {
+      Ok(BigDecimal::from(v))
+    }
+  }
+}
+
+/// `decimal` for optional fields, use with `#[serde(default)]`.
+pub(crate) mod option_decimal {
+  use bigdecimal::BigDecimal;
+  use serde::de::{Deserialize, Deserializer};
+  use serde::ser::Serializer;
+  use serde_derive::Deserialize;
+
+  pub fn serialize<S>(v: &Option<BigDecimal>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match v {
+      Some(v) => super::decimal::serialize(v, serializer),
+      None => serializer.serialize_none(),
+    }
+  }
+
+  pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<BigDecimal>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    struct Decimal(#[serde(with = "super::decimal")] BigDecimal);
+    Ok(Option::<Decimal>::deserialize(deserializer)?.map(|v| v.0))
+  }
+}
+
+/// Decimals written as text like feed documents do, e.g. `<MSRP>99.90</MSRP>`.
+/// Read with `deserialize_str` so XML elements decode too.
+pub(crate) mod decimal_text {
+  use bigdecimal::BigDecimal;
+  use serde::de::{Deserialize, Deserializer, Error};
+  use serde::ser::Serializer;
+  use std::borrow::Cow;
+  use std::str::FromStr;
+
+  pub fn serialize<S>(v: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(v)
+  }
+
+  pub fn deserialize<'de, D>(deserializer: D) -> Result<BigDecimal, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let v = Cow::<str>::deserialize(deserializer)?;
+    BigDecimal::from_str(v.trim()).map_err(D::Error::custom)
+  }
+}
+
+/// `decimal_text` for optional fields, use with `#[serde(default)]`.
+pub(crate) mod option_decimal_text {
+  use bigdecimal::BigDecimal;
+  use serde::de::{Deserialize, Deserializer};
+  use serde::ser::Serializer;
+  use serde_derive::Deserialize;
+
+  pub fn serialize<S>(v: &Option<BigDecimal>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match v {
+      Some(v) => super::decimal_text::serialize(v, serializer),
+      None => serializer.serialize_none(),
+    }
+  }
+
+  pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<BigDecimal>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    struct Decimal(#[serde(with = "super::decimal_text")] BigDecimal);
+    Ok(Option::<Decimal>::deserialize(deserializer)?.map(|v| v.0))
+  }
+}
+
+/// Deserialize bool from Newegg's "True"/"False" strings, empty means false
+pub(crate) fn bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
-  F: FnOnce() -> NeweggResult<R> + Send + 'static,
-  R: Send + 'static,
+  D: Deserializer<'de>,
 {
-  tokio::task::spawn_blocking(f).await.map_err(NeweggError::from)?
-}
\ No newline at end of file
+  match String::deserialize(deserializer)?.as_ref() {
+    "True" | "true" => Ok(true),
+    "False" | "false" | "" => Ok(false),
+    other => Err(de::Error::invalid_value(
+      de::Unexpected::Str(other),
+      &"True or False",
+    )),
+  }
+}
+
+/// Optional Newegg "True"/"False" flag, written back the same way.
+/// Use with `#[serde(default, skip_serializing_if = "Option::is_none")]`.
+pub(crate) mod option_bool_string {
+  use serde::de::{Deserialize, Deserializer};
+  use serde::ser::Serializer;
+  use serde_derive::Deserialize;
+
+  pub fn serialize<S>(value: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match *value {
+      Some(true) => serializer.serialize_str("True"),
+      Some(false) => serializer.serialize_str("False"),
+      None => serializer.serialize_none(),
+    }
+  }
+
+  pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    struct Flag(#[serde(deserialize_with = "super::bool_from_string")] bool);
+    Ok(Option::<Flag>::deserialize(deserializer)?.map(|v| v.0))
+  }
+}
+
+/// Optional flag sent as `1`/`0`, e.g. `OrderDownloaded`.
+/// Use with `#[serde(skip_serializing_if = "Option::is_none")]`.
+pub(crate) fn serialize_option_bool_number<S>(
+  value: &Option<bool>,
+  serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+  S: ::serde::Serializer,
+{
+  match *value {
+    Some(v) => serializer.serialize_u8(u8::from(v)),
+    None => serializer.serialize_none(),
+  }
+}
+
+#[test]
+fn test_newegg_datetime_dst() {
+  let parse = |s: &str| serde_json::from_value::<NeweggDateTime>(serde_json::json!(s));
+  let pdt = parse("02/12/2018 12:16:43").unwrap();
+  assert_eq!(pdt.as_utc().to_rfc3339(), "2018-02-12T20:16:43+00:00");
+  // 01:30 happens twice when DST ends, 02:30 never when it starts
+  assert!(parse("11/04/2018 01:30:00").is_err());
+  assert!(parse("03/11/2018 02:30:00").is_err());
+}
@@ -1,12 +1,29 @@
+// `new()` returning a builder is the request builder idiom used throughout
+// the crate, and `NeweggError::ShipOrderNotSuccess` carries the whole
+// response by value, boxing it would break callers matching on it.
+#![allow(clippy::new_ret_no_self, clippy::result_large_err)]
+
 #[macro_use]
 mod helpers;
+mod poll;
 mod types;
 
 pub use self::client::NeweggPlatform;
 pub use self::types::*;
+pub mod accounts;
 pub mod client;
+pub mod config;
+pub mod content;
 pub mod feed;
 pub mod order;
+pub mod rate_limit;
 pub mod report;
 pub mod result;
+pub mod retry;
+pub mod rma;
+pub mod sbn;
+pub mod seller;
 pub mod service_status;
+pub mod shipping_label;
+pub mod sync;
+pub mod transfer;
@@ -1,6 +1,8 @@
 use crate::client::*;
 use crate::result::{NeweggError, NeweggResult};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::VecDeque;
 
 mod types;
 
@@ -9,6 +11,17 @@ pub use self::types::*;
 #[async_trait]
 pub trait OrderApi {
   async fn get_order_info(&self, request: &GetOrderInfoRequest) -> NeweggResult<GetOrderInfoResponse>;
+  /// Streams every order matching `request`, fetching the next page only when
+  /// the previous one has been consumed.
+  ///
+  /// Paging starts at the request's `PageIndex` (or 1) and stops after
+  /// `PageInfo::total_page_count`, an empty page, `options.max_pages` or the first error.
+  /// Dropping the stream stops further requests.
+  fn get_order_info_stream<'a>(
+    &'a self,
+    request: GetOrderInfoRequest,
+    options: OrderInfoStreamOptions,
+  ) -> BoxStream<'a, NeweggResult<OrderInfo>>;
   async fn cancel_order(
     &self,
     order_number: i64,
@@ -26,33 +39,101 @@ impl OrderApi for NeweggClient {
   /// Note for USA marketplace, the default Status is '0'(Unshipped)
   async fn get_order_info(&self, request: &GetOrderInfoRequest) -> NeweggResult<GetOrderInfoResponse> {
     self
-      .request(Method::PUT, "/ordermgmt/order/orderinfo")
-      .json(&request)
-      .send()
+      .send(
+        self
+          .request(Method::PUT, "/ordermgmt/order/orderinfo")
+          .json(&request),
+      )
       .await?
       .get_response()
       .await
   }
 
+  fn get_order_info_stream<'a>(
+    &'a self,
+    request: GetOrderInfoRequest,
+    options: OrderInfoStreamOptions,
+  ) -> BoxStream<'a, NeweggResult<OrderInfo>> {
+    struct State {
+      request: GetOrderInfoRequest,
+      page_index: i32,
+      pages_fetched: u32,
+      buffer: VecDeque<OrderInfo>,
+      done: bool,
+    }
+
+    let state = State {
+      page_index: request.request_body.page_index.unwrap_or(1),
+      request,
+      pages_fetched: 0,
+      buffer: VecDeque::new(),
+      done: false,
+    };
+
+    stream::unfold(state, move |mut state| async move {
+      loop {
+        if let Some(info) = state.buffer.pop_front() {
+          return Some((Ok(info), state));
+        }
+
+        if state.done {
+          return None;
+        }
+
+        if state.pages_fetched > 0 && !options.page_delay.is_zero() {
+          tokio::time::sleep(options.page_delay).await;
+        }
+
+        state.request.request_body.page_index = Some(state.page_index);
+        let res = match self.get_order_info(&state.request).await {
+          Ok(res) => res,
+          Err(err) => {
+            state.done = true;
+            return Some((Err(err), state));
+          }
+        };
+
+        state.pages_fetched += 1;
+        let total_page_count = res.response_body.page_info.total_page_count;
+        if res.is_empty()
+          || i64::from(state.page_index) >= total_page_count
+          || options
+            .max_pages
+            .map(|max| state.pages_fetched >= max)
+            .unwrap_or(false)
+        {
+          state.done = true;
+        }
+        state.page_index += 1;
+        state
+          .buffer
+          .extend(res.response_body.order_info_list.unwrap_or_default());
+      }
+    })
+    .boxed()
+  }
+
   async fn cancel_order(
     &self,
     order_number: i64,
     reason: CancelOrderReasonCode,
   ) -> NeweggResult<CancelOrderResponse> {
     let res: CancelOrderResponse = self
-      .request(
-        Method::PUT,
-        &format!("/ordermgmt/orderstatus/orders/{}", order_number),
+      .send_non_idempotent(
+        self
+          .request(
+            Method::PUT,
+            &format!("/ordermgmt/orderstatus/orders/{}", order_number),
+          )
+          .json(&CancelOrderAction::new(reason)),
       )
-      .json(&CancelOrderAction::new(reason))
-      .send()
       .await?
       .get_response()
       .await?;
     if res.is_success() {
       Ok(res)
     } else {
-      Err(NeweggError::CancelOrderNotSuccess(res).into())
+      Err(NeweggError::CancelOrderNotSuccess(res))
     }
   }
 
@@ -62,19 +143,21 @@ impl OrderApi for NeweggClient {
     action: &ShipOrderAction,
   ) -> NeweggResult<ShipOrderResponse> {
     let res: ShipOrderResponse = self
-      .request(
-        Method::PUT,
-        &format!("/ordermgmt/orderstatus/orders/{}", order_number),
+      .send_non_idempotent(
+        self
+          .request(
+            Method::PUT,
+            &format!("/ordermgmt/orderstatus/orders/{}", order_number),
+          )
+          .json(action),
       )
-      .json(action)
-      .send()
       .await?
       .get_response()
       .await?;
     if res.is_success() {
       Ok(res)
     } else {
-      Err(NeweggError::ShipOrderNotSuccess(res).into())
+      Err(NeweggError::ShipOrderNotSuccess(res))
     }
   }
 }
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 use crate::helpers::NeweggDateTime;
 
@@ -275,7 +277,7 @@ enum_number! {
 
 impl GetOrderInfoRequestBuilder {
   pub fn finalize(&mut self) -> GetOrderInfoRequest {
-    let mut req = ::std::mem::replace(&mut self.inner, GetOrderInfoRequest::default());
+    let mut req = ::std::mem::take(&mut self.inner);
     if req.request_body.page_size.is_none() {
       req.request_body.page_size = Some(100);
     }
@@ -345,6 +347,31 @@ impl GetOrderInfoRequestBuilder {
   }
 }
 
+/// Options for `OrderApi::get_order_info_stream`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderInfoStreamOptions {
+  /// Delay between two consecutive page requests.
+  pub page_delay: Duration,
+  /// Stop after this many pages even if more are available.
+  pub max_pages: Option<u32>,
+}
+
+impl OrderInfoStreamOptions {
+  pub fn page_delay(self, v: Duration) -> Self {
+    Self {
+      page_delay: v,
+      ..self
+    }
+  }
+
+  pub fn max_pages(self, v: u32) -> Self {
+    Self {
+      max_pages: Some(v),
+      ..self
+    }
+  }
+}
+
 impl GetOrderInfoRequest {
   pub fn new() -> GetOrderInfoRequestBuilder {
     GetOrderInfoRequestBuilder {
@@ -391,7 +418,7 @@ impl CancelOrderAction {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum CancelOrderReasonCode {
   #[serde(rename = "24")]
   OutOfStock,
@@ -437,6 +464,14 @@ impl ShipOrderAction {
   pub fn new(seller_id: &str, order_number: i64) -> ShipOrderActionBuilder {
     ShipOrderActionBuilder::new(seller_id, order_number)
   }
+
+  pub fn order_number(&self) -> i64 {
+    self.value.shipment.header.so_number
+  }
+
+  pub fn packages(&self) -> &[Package] {
+    &self.value.shipment.package_list.package
+  }
 }
 
 pub struct ShipOrderActionBuilder {
@@ -520,15 +555,15 @@ pub enum ShipCarrier {
   Other(String),
 }
 
-impl ToString for ShipCarrier {
-  fn to_string(&self) -> String {
+impl fmt::Display for ShipCarrier {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match *self {
-      ShipCarrier::Ups => "UPS".to_string(),
-      ShipCarrier::UpsMi => "UPS MI".to_string(),
-      ShipCarrier::FedEx => "FedEx".to_string(),
-      ShipCarrier::Dhl => "DHL".to_string(),
-      ShipCarrier::Usps => "USPS".to_string(),
-      ShipCarrier::Other(ref other) => other.to_string(),
+      ShipCarrier::Ups => f.write_str("UPS"),
+      ShipCarrier::UpsMi => f.write_str("UPS MI"),
+      ShipCarrier::FedEx => f.write_str("FedEx"),
+      ShipCarrier::Dhl => f.write_str("DHL"),
+      ShipCarrier::Usps => f.write_str("USPS"),
+      ShipCarrier::Other(ref other) => f.write_str(other),
     }
   }
 }
@@ -571,6 +606,25 @@ impl Package {
   pub fn new(carrier: ShipCarrier, service: &str, tracking: &str) -> PackageBuilder {
     PackageBuilder::new(carrier, service, tracking)
   }
+
+  pub fn tracking_number(&self) -> &str {
+    &self.tracking_number
+  }
+
+  pub fn ship_carrier(&self) -> &str {
+    &self.ship_carrier
+  }
+
+  pub fn ship_service(&self) -> &str {
+    &self.ship_service
+  }
+
+  pub fn items(&self) -> &[ItemElement] {
+    match self.item_list.item {
+      ItemUnion::ItemElement(ref item) => std::slice::from_ref(item),
+      ItemUnion::ItemElementArray(ref items) => items,
+    }
+  }
 }
 
 pub struct PackageBuilder {
@@ -645,6 +699,16 @@ pub struct ItemElement {
   shipped_qty: String,
 }
 
+impl ItemElement {
+  pub fn seller_part_number(&self) -> &str {
+    &self.seller_part_number
+  }
+
+  pub fn shipped_qty(&self) -> &str {
+    &self.shipped_qty
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ItemUnion {
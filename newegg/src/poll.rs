@@ -0,0 +1,183 @@
+//! Polling of submitted feed and report requests.
+
+use serde_derive::{Deserialize, Serialize};
+use std::future::Future;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::result::{NeweggError, NeweggResult};
+
+/// How often and how long to poll a submitted feed or report.
+#[derive(Debug, Clone, Copy)]
+pub struct PollOptions {
+  /// Delay between two status requests.
+  pub poll_interval: Duration,
+  /// Give up if the request is not finished after this long.
+  pub timeout: Duration,
+}
+
+impl Default for PollOptions {
+  fn default() -> Self {
+    PollOptions {
+      poll_interval: Duration::from_secs(30),
+      timeout: Duration::from_secs(60 * 60),
+    }
+  }
+}
+
+impl PollOptions {
+  pub fn poll_interval(self, v: Duration) -> Self {
+    Self {
+      poll_interval: v,
+      ..self
+    }
+  }
+
+  pub fn timeout(self, v: Duration) -> Self {
+    Self { timeout: v, ..self }
+  }
+}
+
+/// Status of a submitted feed or report request.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RequestStatus {
+  Submitted,
+  InProgress,
+  Finished,
+  Cancelled,
+}
+
+impl FromStr for RequestStatus {
+  type Err = NeweggError;
+
+  fn from_str(s: &str) -> NeweggResult<Self> {
+    match s.trim() {
+      "SUBMITTED" => Ok(RequestStatus::Submitted),
+      "IN_PROGRESS" => Ok(RequestStatus::InProgress),
+      "FINISHED" => Ok(RequestStatus::Finished),
+      "CANCELLED" => Ok(RequestStatus::Cancelled),
+      other => Err(NeweggError::UnknownRequestStatus(other.to_string())),
+    }
+  }
+}
+
+/// What is being polled, picks the errors of a request that never finishes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Polled {
+  Feed,
+  Report,
+}
+
+impl Polled {
+  fn cancelled(self, id: &str) -> NeweggError {
+    match self {
+      Polled::Feed => NeweggError::FeedCancelled(id.to_string()),
+      Polled::Report => NeweggError::ReportCancelled(id.to_string()),
+    }
+  }
+
+  fn timed_out(self, id: &str) -> NeweggError {
+    match self {
+      Polled::Feed => NeweggError::FeedTimeout(id.to_string()),
+      Polled::Report => NeweggError::ReportTimeout(id.to_string()),
+    }
+  }
+}
+
+/// One step of polling a request status: `Ok(true)` once it is finished,
+/// `Ok(false)` to poll again, an error if it was cancelled or `timeout` elapsed.
+pub(crate) fn poll_step(
+  polled: Polled,
+  id: &str,
+  status: Option<RequestStatus>,
+  elapsed: Duration,
+  timeout: Duration,
+) -> NeweggResult<bool> {
+  match status {
+    Some(RequestStatus::Finished) => Ok(true),
+    Some(RequestStatus::Cancelled) => Err(polled.cancelled(id)),
+    _ if elapsed >= timeout => Err(polled.timed_out(id)),
+    _ => Ok(false),
+  }
+}
+
+/// Calls `fetch` every `options.poll_interval` until it returns a finished
+/// status, then returns the value that came with it.
+pub(crate) async fn poll_until_finished<T, F, Fut>(
+  polled: Polled,
+  id: &str,
+  options: PollOptions,
+  mut fetch: F,
+) -> NeweggResult<T>
+where
+  F: FnMut() -> Fut,
+  Fut: Future<Output = NeweggResult<Option<(RequestStatus, T)>>>,
+{
+  let started_at = Instant::now();
+  loop {
+    let fetched = fetch().await?;
+    let status = fetched.as_ref().map(|(status, _)| *status);
+    if poll_step(polled, id, status, started_at.elapsed(), options.timeout)? {
+      if let Some((_, value)) = fetched {
+        return Ok(value);
+      }
+    }
+    tokio::time::sleep(options.poll_interval).await;
+  }
+}
+
+#[test]
+fn test_poll_step() {
+  let timeout = Duration::from_secs(60);
+  let step = |status, elapsed| {
+    poll_step(
+      Polled::Report,
+      "R1",
+      status,
+      Duration::from_secs(elapsed),
+      timeout,
+    )
+  };
+
+  assert!(!step(None, 0).unwrap());
+  assert!(!step(Some(RequestStatus::Submitted), 10).unwrap());
+  assert!(!step(Some(RequestStatus::InProgress), 59).unwrap());
+  assert!(step(Some(RequestStatus::Finished), 0).unwrap());
+  // a finished report is used even if it took too long
+  assert!(step(Some(RequestStatus::Finished), 120).unwrap());
+  match step(Some(RequestStatus::InProgress), 60) {
+    Err(NeweggError::ReportTimeout(id)) => assert_eq!(id, "R1"),
+    other => panic!("unexpected result: {:?}", other),
+  }
+  match step(Some(RequestStatus::Cancelled), 0) {
+    Err(NeweggError::ReportCancelled(id)) => assert_eq!(id, "R1"),
+    other => panic!("unexpected result: {:?}", other),
+  }
+  match poll_step(
+    Polled::Feed,
+    "F1",
+    Some(RequestStatus::Cancelled),
+    Duration::ZERO,
+    timeout,
+  ) {
+    Err(NeweggError::FeedCancelled(id)) => assert_eq!(id, "F1"),
+    other => panic!("unexpected result: {:?}", other),
+  }
+}
+
+#[test]
+fn test_request_status_from_str() {
+  assert_eq!(
+    "IN_PROGRESS".parse::<RequestStatus>().unwrap(),
+    RequestStatus::InProgress
+  );
+  assert_eq!(
+    " FINISHED".parse::<RequestStatus>().unwrap(),
+    RequestStatus::Finished
+  );
+  match "FAILED".parse::<RequestStatus>() {
+    Err(NeweggError::UnknownRequestStatus(status)) => assert_eq!(status, "FAILED"),
+    other => panic!("unexpected result: {:?}", other),
+  }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::service_status::ServiceStatusDomain;
+
+/// Allows `capacity` requests per `per`, with bursts of up to `capacity` requests.
+///
+/// A quota with a zero `capacity` or `per` does not limit anything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitQuota {
+  pub capacity: u32,
+  pub per: Duration,
+}
+
+impl RateLimitQuota {
+  pub fn new(capacity: u32, per: Duration) -> Self {
+    RateLimitQuota { capacity, per }
+  }
+
+  pub fn per_second(capacity: u32) -> Self {
+    Self::new(capacity, Duration::from_secs(1))
+  }
+
+  pub fn per_minute(capacity: u32) -> Self {
+    Self::new(capacity, Duration::from_secs(60))
+  }
+
+  pub fn is_unlimited(&self) -> bool {
+    self.capacity == 0 || self.per.is_zero()
+  }
+
+  fn tokens_per_sec(&self) -> f64 {
+    f64::from(self.capacity) / self.per.as_secs_f64()
+  }
+}
+
+#[derive(Debug)]
+struct Bucket {
+  quota: RateLimitQuota,
+  tokens: f64,
+  updated_at: Instant,
+}
+
+impl Bucket {
+  fn new(quota: RateLimitQuota) -> Self {
+    Bucket {
+      quota,
+      tokens: f64::from(quota.capacity),
+      updated_at: Instant::now(),
+    }
+  }
+
+  /// Takes a token, or returns how long to wait until one is available.
+  fn try_take(&mut self) -> Result<(), Duration> {
+    if self.quota.is_unlimited() {
+      return Ok(());
+    }
+    let now = Instant::now();
+    let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+    self.tokens =
+      (self.tokens + elapsed * self.quota.tokens_per_sec()).min(f64::from(self.quota.capacity));
+    self.updated_at = now;
+
+    if self.tokens >= 1.0 {
+      self.tokens -= 1.0;
+      Ok(())
+    } else {
+      Err(Duration::from_secs_f64(
+        (1.0 - self.tokens) / self.quota.tokens_per_sec(),
+      ))
+    }
+  }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+  default_quota: Option<RateLimitQuota>,
+  buckets: HashMap<ServiceStatusDomain, Bucket>,
+}
+
+/// Token-bucket rate limiter keyed by Newegg API domain
+/// (`ordermgmt`, `datafeedmgmt`, `reportmgmt`, ...).
+///
+/// Clones share the same buckets, so one limiter can be attached to every
+/// `NeweggClient` of a process that uses the same seller account. Use
+/// `unshared` for the limiter of another account.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+  inner: Arc<Mutex<Inner>>,
+}
+
+impl RateLimiter {
+  /// Creates a limiter that only limits domains configured with `set_quota`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a limiter that applies `quota` to every domain without an explicit quota.
+  pub fn with_default_quota(quota: RateLimitQuota) -> Self {
+    let limiter = Self::default();
+    limiter.lock().default_quota = Some(quota);
+    limiter
+  }
+
+  pub fn quota(self, domain: ServiceStatusDomain, quota: RateLimitQuota) -> Self {
+    self.set_quota(domain, quota);
+    self
+  }
+
+  pub fn set_quota(&self, domain: ServiceStatusDomain, quota: RateLimitQuota) {
+    self.lock().buckets.insert(domain, Bucket::new(quota));
+  }
+
+  /// A limiter with the same quotas and full buckets of its own.
+  pub fn unshared(&self) -> Self {
+    let inner = self.lock();
+    let limiter = RateLimiter {
+      inner: Arc::new(Mutex::new(Inner {
+        default_quota: inner.default_quota,
+        buckets: HashMap::new(),
+      })),
+    };
+    for (domain, bucket) in &inner.buckets {
+      limiter.set_quota(*domain, bucket.quota);
+    }
+    limiter
+  }
+
+  /// Waits until a request to `domain` is allowed.
+  pub async fn acquire(&self, domain: ServiceStatusDomain) {
+    loop {
+      let wait = {
+        let mut inner = self.lock();
+        let default_quota = inner.default_quota;
+        let bucket = match inner.buckets.get_mut(&domain) {
+          Some(bucket) => bucket,
+          None => match default_quota {
+            Some(quota) => inner.buckets.entry(domain).or_insert_with(|| Bucket::new(quota)),
+            None => return,
+          },
+        };
+        match bucket.try_take() {
+          Ok(()) => return,
+          Err(wait) => wait,
+        }
+      };
+      tokio::time::sleep(wait).await;
+    }
+  }
+
+  fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+    self.inner.lock().unwrap_or_else(|err| err.into_inner())
+  }
+}
+
+#[test]
+fn test_bucket_refill() {
+  let mut bucket = Bucket::new(RateLimitQuota::per_second(2));
+  assert!(bucket.try_take().is_ok());
+  assert!(bucket.try_take().is_ok());
+  let wait = bucket.try_take().unwrap_err();
+  assert!(wait > Duration::from_millis(0) && wait <= Duration::from_millis(500));
+}
+
+#[test]
+fn test_zero_quota_is_unlimited() {
+  for quota in &[
+    RateLimitQuota::per_minute(0),
+    RateLimitQuota::new(10, Duration::from_secs(0)),
+  ] {
+    let mut bucket = Bucket::new(*quota);
+    for _ in 0..100 {
+      assert!(bucket.try_take().is_ok());
+    }
+  }
+}
+
+#[test]
+fn test_unshared_buckets() {
+  let limiter =
+    RateLimiter::new().quota(ServiceStatusDomain::Order, RateLimitQuota::per_minute(1));
+  let other = limiter.unshared();
+  for limiter in &[&limiter, &other] {
+    let mut inner = limiter.lock();
+    let bucket = inner.buckets.get_mut(&ServiceStatusDomain::Order).unwrap();
+    assert!(bucket.try_take().is_ok());
+    assert!(bucket.try_take().is_err());
+  }
+}
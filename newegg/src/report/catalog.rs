@@ -0,0 +1,725 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+use super::ReportRequest;
+use crate::client::NeweggPlatform;
+use crate::helpers::{bool_from_string, NeweggDateTime};
+use crate::order::{OrderInfo, OrderStatus, OrderTypeFilter, PageInfo, PremierOrderFilter};
+use crate::poll::PollOptions;
+use crate::result::{NeweggError, NeweggResult};
+use crate::rma::{RmaInfo, RmaKeywordsType, RmaStatus, RmaType};
+use crate::types::MaybeList;
+
+/// Report types supported by the report management API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportKind {
+  OrderList,
+  UnshippedOrder,
+  SettlementSummary,
+  SettlementTransaction,
+  RmaList,
+  ItemLookup,
+  DailyInventory,
+  DailyPrice,
+  InternationalInventory,
+  InternationalPrice,
+}
+
+impl ReportKind {
+  /// `OperationType` of the submit and result requests.
+  pub fn operation_type(&self) -> &'static str {
+    match *self {
+      ReportKind::OrderList => "OrderListReportRequest",
+      ReportKind::UnshippedOrder => "UnshippedOrderReportRequest",
+      ReportKind::SettlementSummary => "SettlementSummaryReportRequest",
+      ReportKind::SettlementTransaction => "SettlementTransactionReportRequest",
+      ReportKind::RmaList => "RMAListReportRequest",
+      ReportKind::ItemLookup => "ItemLookupReportRequest",
+      ReportKind::DailyInventory => "DailyInventoryReportRequest",
+      ReportKind::DailyPrice => "DailyPriceReportRequest",
+      ReportKind::InternationalInventory => "InternationalInventoryReportRequest",
+      ReportKind::InternationalPrice => "InternationalPriceReportRequest",
+    }
+  }
+
+  /// `RequestType` inside the report criteria.
+  pub fn request_type(&self) -> &'static str {
+    match *self {
+      ReportKind::OrderList => "ORDER_LIST_REPORT",
+      ReportKind::UnshippedOrder => "UNSHIPPED_ORDER_REPORT",
+      ReportKind::SettlementSummary => "SETTLEMENT_SUMMARY_REPORT",
+      ReportKind::SettlementTransaction => "SETTLEMENT_TRANSACTION_REPORT",
+      ReportKind::RmaList => "RMA_LIST_REPORT",
+      ReportKind::ItemLookup => "ITEM_LOOKUP_REPORT",
+      ReportKind::DailyInventory => "DAILY_INVENTORY_REPORT",
+      ReportKind::DailyPrice => "DAILY_PRICE_REPORT",
+      ReportKind::InternationalInventory => "INTERNATIONAL_INVENTORY_REPORT",
+      ReportKind::InternationalPrice => "INTERNATIONAL_PRICE_REPORT",
+    }
+  }
+
+  /// Name of the criteria object in the request body.
+  pub fn criteria_name(&self) -> &'static str {
+    match *self {
+      ReportKind::OrderList | ReportKind::UnshippedOrder => "OrderReportCriteria",
+      ReportKind::SettlementSummary => "SettlementSummaryReportCriteria",
+      ReportKind::SettlementTransaction => "SettlementTransactionReportCriteria",
+      ReportKind::RmaList => "RMAReportCriteria",
+      ReportKind::ItemLookup => "ItemLookupReportCriteria",
+      ReportKind::DailyInventory | ReportKind::InternationalInventory => {
+        "DailyInventoryReportCriteria"
+      }
+      ReportKind::DailyPrice | ReportKind::InternationalPrice => "DailyPriceReportCriteria",
+    }
+  }
+
+  /// Name of the row list in the report result body.
+  /// `None` for reports delivered as a file through `ReportFileURL`.
+  pub fn result_list_name(&self) -> Option<&'static str> {
+    match *self {
+      ReportKind::OrderList | ReportKind::UnshippedOrder => Some("OrderInfoList"),
+      ReportKind::SettlementSummary => Some("SettlementSummaryList"),
+      ReportKind::SettlementTransaction => Some("SettlementTransactionInfoList"),
+      ReportKind::RmaList => Some("RMAInfoList"),
+      ReportKind::ItemLookup => Some("ItemInfoList"),
+      ReportKind::DailyInventory
+      | ReportKind::DailyPrice
+      | ReportKind::InternationalInventory
+      | ReportKind::InternationalPrice => None,
+    }
+  }
+
+  pub fn is_file_report(&self) -> bool {
+    self.result_list_name().is_none()
+  }
+
+  /// The inventory report available on `platform`.
+  pub fn inventory(platform: NeweggPlatform) -> Self {
+    match platform.marketplace() {
+      NeweggPlatform::Newegg => ReportKind::InternationalInventory,
+      _ => ReportKind::DailyInventory,
+    }
+  }
+
+  /// The price report available on `platform`.
+  pub fn price(platform: NeweggPlatform) -> Self {
+    match platform.marketplace() {
+      NeweggPlatform::Newegg => ReportKind::InternationalPrice,
+      _ => ReportKind::DailyPrice,
+    }
+  }
+}
+
+/// Typed criteria of a report request.
+///
+/// The criteria is serialized without `RequestType`, which is filled in from `kind`.
+pub trait ReportCriteria: Serialize + Send + Sync {
+  /// Row type of the report result or report file.
+  type Row: for<'de> Deserialize<'de> + Send;
+
+  fn kind(&self, platform: NeweggPlatform) -> ReportKind;
+
+  /// File type requested by the criteria, used to decode the report file.
+  fn file_type(&self) -> Option<ReportFileType> {
+    None
+  }
+}
+
+impl ReportRequest<Value> {
+  pub fn from_criteria<C: ReportCriteria>(criteria: &C, platform: NeweggPlatform) -> NeweggResult<Self> {
+    let kind = criteria.kind(platform);
+    let mut fields = match serde_json::to_value(criteria)? {
+      Value::Object(fields) => fields,
+      other => {
+        return Err(NeweggError::Deserialize {
+          msg: "report criteria is not an object".to_string(),
+          body: other.to_string(),
+        })
+      }
+    };
+    fields.insert("RequestType".to_string(), kind.request_type().into());
+    let mut body = Map::new();
+    body.insert(kind.criteria_name().to_string(), Value::Object(fields));
+    Ok(ReportRequest::new(kind.operation_type(), Value::Object(body)))
+  }
+}
+
+/// Options for `ReportApi::run_report`.
+#[derive(Debug, Clone, Copy)]
+pub struct RunReportOptions {
+  /// Polling of the report status.
+  pub poll: PollOptions,
+  /// Page size used to fetch report results.
+  pub page_size: u64,
+}
+
+impl Default for RunReportOptions {
+  fn default() -> Self {
+    RunReportOptions {
+      poll: PollOptions::default(),
+      page_size: 100,
+    }
+  }
+}
+
+impl RunReportOptions {
+  pub fn poll(self, v: PollOptions) -> Self {
+    Self { poll: v, ..self }
+  }
+
+  pub fn poll_interval(self, v: Duration) -> Self {
+    Self {
+      poll: self.poll.poll_interval(v),
+      ..self
+    }
+  }
+
+  pub fn timeout(self, v: Duration) -> Self {
+    Self {
+      poll: self.poll.timeout(v),
+      ..self
+    }
+  }
+
+  pub fn page_size(self, v: u64) -> Self {
+    Self {
+      page_size: v,
+      ..self
+    }
+  }
+}
+
+/// One page of a report result.
+#[derive(Debug)]
+pub struct ReportPage<T> {
+  pub page_info: Option<PageInfo>,
+  pub report_file_url: Option<String>,
+  pub rows: Vec<T>,
+}
+
+impl<T> ReportPage<T>
+where
+  T: for<'de> Deserialize<'de>,
+{
+  pub fn from_response_body(kind: ReportKind, mut body: Value) -> NeweggResult<Self> {
+    let page_info = match body.get_mut("PageInfo").map(Value::take) {
+      Some(Value::Null) | None => None,
+      Some(v) => Some(serde_json::from_value(v)?),
+    };
+    let report_file_url = body
+      .get("ReportFileURL")
+      .and_then(Value::as_str)
+      .map(ToString::to_string);
+    let rows = match kind
+      .result_list_name()
+      .and_then(|name| body.get_mut(name))
+      .map(Value::take)
+    {
+      Some(Value::Null) | None => vec![],
+      Some(v) => serde_json::from_value::<MaybeList<T>>(v)?.into_vec(),
+    };
+    Ok(ReportPage {
+      page_info,
+      report_file_url,
+      rows,
+    })
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ReportFileType {
+  #[serde(rename = "CSV")]
+  Csv,
+  #[serde(rename = "XLS")]
+  Xls,
+  #[serde(rename = "TXT")]
+  Txt,
+}
+
+impl ReportFileType {
+  /// Guesses the file type from the extension of a report file URL.
+  pub fn from_url(url: &str) -> Option<Self> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let ext = path.rsplit('.').next()?.to_lowercase();
+    match ext.as_ref() {
+      "csv" => Some(ReportFileType::Csv),
+      "txt" => Some(ReportFileType::Txt),
+      "xls" | "xlsx" => Some(ReportFileType::Xls),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum FulfillType {
+  #[serde(rename = "0")]
+  All,
+  #[serde(rename = "1")]
+  ShippedBySeller,
+  #[serde(rename = "2")]
+  ShippedByNewegg,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/reports_management/submit_report_request/order_list_report/
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct OrderListReportCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Status")]
+  pub status: Option<OrderStatus>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Type")]
+  pub type_: Option<OrderTypeFilter>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderDateFrom")]
+  pub order_date_from: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderDateTo")]
+  pub order_date_to: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderDownloaded", serialize_with = "crate::helpers::serialize_option_bool_number")]
+  pub order_downloaded: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CountryCode")]
+  pub country_code: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PremierOrder")]
+  pub premier_order: Option<PremierOrderFilter>,
+}
+
+impl ReportCriteria for OrderListReportCriteria {
+  type Row = OrderInfo;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::OrderList
+  }
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct UnshippedOrderReportCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderDateFrom")]
+  pub order_date_from: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "OrderDateTo")]
+  pub order_date_to: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CountryCode")]
+  pub country_code: Option<String>,
+}
+
+impl ReportCriteria for UnshippedOrderReportCriteria {
+  type Row = OrderInfo;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::UnshippedOrder
+  }
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/reports_management/submit_report_request/settlement_summary_report/
+#[derive(Debug, Serialize, Clone)]
+pub struct SettlementSummaryReportCriteria {
+  #[serde(rename = "DateFrom")]
+  pub date_from: NeweggDateTime,
+  #[serde(rename = "DateTo")]
+  pub date_to: NeweggDateTime,
+}
+
+impl ReportCriteria for SettlementSummaryReportCriteria {
+  type Row = SettlementSummaryRow;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::SettlementSummary
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SettlementSummaryRow {
+  #[serde(rename = "SettlementDate")]
+  pub settlement_date: Option<NeweggDateTime>,
+  #[serde(rename = "SettlementDateFrom")]
+  pub settlement_date_from: Option<NeweggDateTime>,
+  #[serde(rename = "SettlementDateTo")]
+  pub settlement_date_to: Option<NeweggDateTime>,
+  #[serde(rename = "SettlementID")]
+  pub settlement_id: String,
+  #[serde(rename = "CheckNumber")]
+  pub check_number: Option<String>,
+  #[serde(rename = "ItemPrice", default, with = "crate::helpers::option_decimal")]
+  pub item_price: Option<BigDecimal>,
+  #[serde(rename = "Shipping", default, with = "crate::helpers::option_decimal")]
+  pub shipping: Option<BigDecimal>,
+  #[serde(rename = "Other", default, with = "crate::helpers::option_decimal")]
+  pub other: Option<BigDecimal>,
+  #[serde(rename = "OrderTotal", default, with = "crate::helpers::option_decimal")]
+  pub order_total: Option<BigDecimal>,
+  #[serde(rename = "RefundItemPrice", default, with = "crate::helpers::option_decimal")]
+  pub refund_item_price: Option<BigDecimal>,
+  #[serde(rename = "RefundShipping", default, with = "crate::helpers::option_decimal")]
+  pub refund_shipping: Option<BigDecimal>,
+  #[serde(rename = "RefundOther", default, with = "crate::helpers::option_decimal")]
+  pub refund_other: Option<BigDecimal>,
+  #[serde(rename = "RefundTotal", default, with = "crate::helpers::option_decimal")]
+  pub refund_total: Option<BigDecimal>,
+  #[serde(rename = "NeweggTransactionFee", default, with = "crate::helpers::option_decimal")]
+  pub newegg_transaction_fee: Option<BigDecimal>,
+  #[serde(rename = "NeweggRefundCommissionFee", default, with = "crate::helpers::option_decimal")]
+  pub newegg_refund_commission_fee: Option<BigDecimal>,
+  #[serde(rename = "NeweggMonthlyFee", default, with = "crate::helpers::option_decimal")]
+  pub newegg_monthly_fee: Option<BigDecimal>,
+  #[serde(rename = "NeweggStorageFee", default, with = "crate::helpers::option_decimal")]
+  pub newegg_storage_fee: Option<BigDecimal>,
+  #[serde(rename = "NeweggRMAFee", default, with = "crate::helpers::option_decimal")]
+  pub newegg_rma_fee: Option<BigDecimal>,
+  #[serde(rename = "ChargeBack", default, with = "crate::helpers::option_decimal")]
+  pub charge_back: Option<BigDecimal>,
+  #[serde(rename = "MiscellaneousAdjustment", default, with = "crate::helpers::option_decimal")]
+  pub miscellaneous_adjustment: Option<BigDecimal>,
+  #[serde(rename = "TotalAmount", default, with = "crate::helpers::option_decimal")]
+  pub total_amount: Option<BigDecimal>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/reports_management/submit_report_request/settlement_transaction_report/
+#[derive(Debug, Serialize, Clone)]
+pub struct SettlementTransactionReportCriteria {
+  #[serde(rename = "DateFrom")]
+  pub date_from: NeweggDateTime,
+  #[serde(rename = "DateTo")]
+  pub date_to: NeweggDateTime,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SettlementID")]
+  pub settlement_id: Option<String>,
+}
+
+impl ReportCriteria for SettlementTransactionReportCriteria {
+  type Row = SettlementTransactionRow;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::SettlementTransaction
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SettlementTransactionRow {
+  #[serde(rename = "SettlementID")]
+  pub settlement_id: String,
+  #[serde(rename = "SettlementDate")]
+  pub settlement_date: Option<NeweggDateTime>,
+  #[serde(rename = "TransactionType")]
+  pub transaction_type: String,
+  #[serde(rename = "OrderDate")]
+  pub order_date: Option<NeweggDateTime>,
+  #[serde(rename = "OrderNumber")]
+  pub order_number: Option<i64>,
+  #[serde(rename = "InvoiceNumber")]
+  pub invoice_number: Option<i64>,
+  #[serde(rename = "SellerOrderNumber")]
+  pub seller_order_number: Option<String>,
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: Option<String>,
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(rename = "ItemDescription")]
+  pub item_description: Option<String>,
+  #[serde(rename = "Quantity")]
+  pub quantity: Option<i64>,
+  #[serde(rename = "Amount", default, with = "crate::helpers::option_decimal")]
+  pub amount: Option<BigDecimal>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/reports_management/submit_report_request/rma_list_report/
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct RmaListReportCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Status")]
+  pub status: Option<RmaStatus>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Type")]
+  pub type_: Option<RmaType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "KeywordsType")]
+  pub keywords_type: Option<RmaKeywordsType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "KeywordsValue")]
+  pub keywords_value: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMADateFrom")]
+  pub rma_date_from: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMADateTo")]
+  pub rma_date_to: Option<NeweggDateTime>,
+}
+
+impl ReportCriteria for RmaListReportCriteria {
+  type Row = RmaInfo;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::RmaList
+  }
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ItemLookupReportCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellerPartNumberList")]
+  pub seller_part_number_list: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "NeweggItemNumberList")]
+  pub newegg_item_number_list: Option<Vec<String>>,
+}
+
+impl ReportCriteria for ItemLookupReportCriteria {
+  type Row = ItemLookupRow;
+
+  fn kind(&self, _: NeweggPlatform) -> ReportKind {
+    ReportKind::ItemLookup
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemLookupRow {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(rename = "ManufacturerPartNumber")]
+  pub manufacturer_part_number: Option<String>,
+  #[serde(rename = "UPC")]
+  pub upc: Option<String>,
+  #[serde(rename = "ItemDescription")]
+  pub item_description: Option<String>,
+  #[serde(rename = "Active")]
+  pub active: Option<String>,
+}
+
+/// Daily inventory report, `DailyInventoryReportRequest` or
+/// `InternationalInventoryReportRequest` depending on the platform.
+#[derive(Debug, Serialize, Clone)]
+pub struct InventoryReportCriteria {
+  #[serde(rename = "FulfillType")]
+  pub fulfill_type: FulfillType,
+  #[serde(rename = "FileType")]
+  pub file_type: ReportFileType,
+}
+
+impl Default for InventoryReportCriteria {
+  fn default() -> Self {
+    InventoryReportCriteria {
+      fulfill_type: FulfillType::All,
+      file_type: ReportFileType::Csv,
+    }
+  }
+}
+
+impl ReportCriteria for InventoryReportCriteria {
+  type Row = InventoryReportRow;
+
+  fn kind(&self, platform: NeweggPlatform) -> ReportKind {
+    ReportKind::inventory(platform)
+  }
+
+  fn file_type(&self) -> Option<ReportFileType> {
+    Some(self.file_type)
+  }
+}
+
+/// Daily price report, `DailyPriceReportRequest` or
+/// `InternationalPriceReportRequest` depending on the platform.
+#[derive(Debug, Serialize, Clone)]
+pub struct PriceReportCriteria {
+  #[serde(rename = "FileType")]
+  pub file_type: ReportFileType,
+}
+
+impl Default for PriceReportCriteria {
+  fn default() -> Self {
+    PriceReportCriteria {
+      file_type: ReportFileType::Csv,
+    }
+  }
+}
+
+impl ReportCriteria for PriceReportCriteria {
+  type Row = PriceReportRow;
+
+  fn kind(&self, platform: NeweggPlatform) -> ReportKind {
+    ReportKind::price(platform)
+  }
+
+  fn file_type(&self) -> Option<ReportFileType> {
+    Some(self.file_type)
+  }
+}
+
+/// Row of the US inventory report file.
+/// Canada and B2B files decode into it too, without the warehouse location,
+/// use `CanInventoryReportRow` for their price columns.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct InventoryReportRow {
+  #[serde(rename = "Seller Part #")]
+  pub seller_part_number: String,
+  #[serde(rename = "NE Item #")]
+  pub ne_item_number: String,
+  #[serde(rename = "Warehouse Location")]
+  pub warehouse_location: String,
+  #[serde(rename = "Fulfillment Option", alias = "Fulfillment option")]
+  pub fulfillment_option: String,
+  #[serde(rename = "Inventory")]
+  pub inventory: i32,
+}
+
+/// Row of the Canada and B2B inventory report file.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CanInventoryReportRow {
+  #[serde(rename = "Seller Part #")]
+  pub seller_part_number: String,
+  #[serde(rename = "NE Item #")]
+  pub ne_item_number: String,
+  #[serde(rename = "Currency")]
+  pub currency: String,
+  #[serde(rename = "MSRP")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(rename = "MAP")]
+  pub map: Option<BigDecimal>,
+  #[serde(rename = "Checkout MAP")]
+  #[serde(deserialize_with = "bool_from_string")]
+  pub checkout_map: bool,
+  #[serde(rename = "Selling Price")]
+  pub selling_price: BigDecimal,
+  #[serde(rename = "Inventory")]
+  pub inventory: i32,
+  #[serde(rename = "Fulfillment option")]
+  pub fulfillment_option: String,
+  #[serde(rename = "Shipping")]
+  pub shipping: String,
+  #[serde(rename = "Activation Mark")]
+  #[serde(deserialize_with = "bool_from_string")]
+  pub activation_mark: bool,
+}
+
+/// Row of the price report file.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PriceReportRow {
+  #[serde(rename = "Seller Part #")]
+  pub seller_part_number: String,
+  #[serde(rename = "NE Item #")]
+  pub ne_item_number: String,
+  #[serde(rename = "Country")]
+  pub country: Option<String>,
+  #[serde(rename = "Currency")]
+  pub currency: Option<String>,
+  #[serde(rename = "MSRP")]
+  pub msrp: Option<BigDecimal>,
+  #[serde(rename = "MAP")]
+  pub map: Option<BigDecimal>,
+  #[serde(rename = "Checkout MAP")]
+  #[serde(deserialize_with = "bool_from_string")]
+  pub checkout_map: bool,
+  #[serde(rename = "Selling Price")]
+  pub selling_price: Option<BigDecimal>,
+  #[serde(rename = "Shipping")]
+  pub shipping: Option<String>,
+  #[serde(rename = "Activation Mark")]
+  #[serde(deserialize_with = "bool_from_string")]
+  pub activation_mark: bool,
+}
+
+#[test]
+fn test_report_request_from_criteria() {
+  let req = ReportRequest::from_criteria(&InventoryReportCriteria::default(), NeweggPlatform::Newegg)
+    .unwrap();
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    serde_json::json!({
+      "OperationType": "InternationalInventoryReportRequest",
+      "RequestBody": {
+        "DailyInventoryReportCriteria": {
+          "FulfillType": "0",
+          "RequestType": "INTERNATIONAL_INVENTORY_REPORT",
+          "FileType": "CSV"
+        }
+      }
+    })
+  );
+
+  let req =
+    ReportRequest::from_criteria(&InventoryReportCriteria::default(), NeweggPlatform::NeweggCanada)
+      .unwrap();
+  assert_eq!(req.operation_type, "DailyInventoryReportRequest");
+  let req =
+    ReportRequest::from_criteria(&PriceReportCriteria::default(), NeweggPlatform::NeweggCanada)
+      .unwrap();
+  assert_eq!(req.operation_type, "DailyPriceReportRequest");
+  let req = ReportRequest::from_criteria(&PriceReportCriteria::default(), NeweggPlatform::Newegg)
+    .unwrap();
+  assert_eq!(req.operation_type, "InternationalPriceReportRequest");
+
+  let criteria = OrderListReportCriteria {
+    status: Some(OrderStatus::Unshipped),
+    type_: Some(OrderTypeFilter::ShippedBySeller),
+    order_downloaded: Some(false),
+    premier_order: Some(PremierOrderFilter::All),
+    ..Default::default()
+  };
+  let req = ReportRequest::from_criteria(&criteria, NeweggPlatform::Newegg).unwrap();
+  assert_eq!(
+    serde_json::to_value(&req.request_body).unwrap(),
+    serde_json::json!({
+      "OrderReportCriteria": {
+        "RequestType": "ORDER_LIST_REPORT",
+        "Status": 0,
+        "Type": 2,
+        "OrderDownloaded": 0,
+        "PremierOrder": 0
+      }
+    })
+  );
+  let criteria = RmaListReportCriteria {
+    status: Some(RmaStatus::Open),
+    type_: Some(RmaType::Refund),
+    keywords_type: Some(RmaKeywordsType::OrderNumber),
+    keywords_value: Some("1001".to_string()),
+    ..Default::default()
+  };
+  let req = ReportRequest::from_criteria(&criteria, NeweggPlatform::Newegg).unwrap();
+  assert_eq!(
+    serde_json::to_value(&req.request_body).unwrap(),
+    serde_json::json!({
+      "RMAReportCriteria": {
+        "RequestType": "RMA_LIST_REPORT",
+        "Status": 1,
+        "Type": 1,
+        "KeywordsType": 2,
+        "KeywordsValue": "1001"
+      }
+    })
+  );
+}
+
+#[test]
+fn test_settlement_rows() {
+  let body = serde_json::json!({
+    "PageInfo": null,
+    "SettlementTransactionInfoList": [{
+      "SettlementID": "S001",
+      "SettlementDate": "2/22/2013",
+      "TransactionType": "Order",
+      "OrderDate": "02/06/2013 14:13:52",
+      "OrderNumber": 123456789,
+      "Amount": 19.99
+    }]
+  });
+  let page: ReportPage<SettlementTransactionRow> =
+    ReportPage::from_response_body(ReportKind::SettlementTransaction, body).unwrap();
+  let row = &page.rows[0];
+  assert_eq!(row.amount, Some("19.99".parse().unwrap()));
+  assert_eq!(
+    row.settlement_date.unwrap().as_utc().to_rfc3339(),
+    "2013-02-22T08:00:00+00:00"
+  );
+  assert!(row.order_date.is_some());
+}
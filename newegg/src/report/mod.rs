@@ -1,20 +1,34 @@
+use futures::stream::{self, BoxStream, StreamExt};
 use serde::Serialize;
-use url::Url;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::client::*;
-use crate::helpers::block;
+use crate::poll::{poll_until_finished, Polled};
 use crate::result::{NeweggError, NeweggResult};
+use crate::transfer::{FileDownload, FileTransfer, TransferUrl};
+use crate::types::NeweggApiResponseWrapped;
 use async_trait::async_trait;
 
+mod catalog;
+pub mod parse;
 mod types;
 
+pub use self::catalog::*;
 pub use self::types::*;
+pub use crate::poll::PollOptions;
 
 #[async_trait]
 pub trait ReportApi {
   async fn submit_report_request<R>(&self, request: ReportRequest<R>) -> NeweggResult<ReportResponse>
   where
     R: Serialize + Send + Sync;
+  /// Submits a typed report request, picking the request variant for the client's platform.
+  async fn submit_report<C>(&self, criteria: &C) -> NeweggResult<ReportResponse>
+  where
+    C: ReportCriteria;
   async fn get_report_status(
     &self,
     request_ids: &[&str],
@@ -27,7 +41,44 @@ pub trait ReportApi {
     page_index: u64,
     page_size: Option<u64>,
   ) -> NeweggResult<ReportResultReponse>;
+  /// Fetches one page of the result of a report submitted with `criteria` as typed rows.
+  /// File reports only carry `ReportPage::report_file_url`.
+  async fn get_report_page<C>(
+    &self,
+    criteria: &C,
+    request_id: &str,
+    page_index: u64,
+    page_size: Option<u64>,
+  ) -> NeweggResult<ReportPage<C::Row>>
+  where
+    C: ReportCriteria;
+  /// Downloads a whole report file into memory.
   async fn get_report_file(&self, url: &str) -> NeweggResult<Vec<u8>>;
+  /// Opens a report file for streaming, starting at byte `offset`.
+  async fn open_report_file(&self, url: &str, offset: u64) -> NeweggResult<FileDownload>;
+  /// Downloads a report file to `path`, resuming from the end of a partially
+  /// downloaded file. Returns the size of the file.
+  async fn save_report_file(&self, url: &str, path: &Path) -> NeweggResult<u64>;
+  /// Polls the status of a submitted report until it is finished.
+  async fn wait_for_report(
+    &self,
+    request_id: &str,
+    options: RunReportOptions,
+  ) -> NeweggResult<ResponseInfo>;
+  /// Submits a report, waits for it to finish and streams every row,
+  /// either from the paged result or from the downloaded report file.
+  ///
+  /// Result pages are fetched as the stream is consumed, the stream ends
+  /// after the first error. Report files are saved to the temporary
+  /// directory and decoded as the stream is consumed, the file is deleted
+  /// when the stream is dropped.
+  fn run_report<'a, C>(
+    &'a self,
+    criteria: &'a C,
+    options: RunReportOptions,
+  ) -> BoxStream<'a, NeweggResult<C::Row>>
+  where
+    C: ReportCriteria;
 }
 
 #[async_trait]
@@ -37,26 +88,38 @@ impl ReportApi for NeweggClient {
     R: Serialize + Send + Sync,
   {
     self
-      .request(Method::POST, "/reportmgmt/report/submitrequest")
-      .json(&request)
-      .send()
+      .send_non_idempotent(
+        self
+          .request(Method::POST, "/reportmgmt/report/submitrequest")
+          .json(&request),
+      )
       .await?
       .get_response()
       .await
   }
 
+  async fn submit_report<C>(&self, criteria: &C) -> NeweggResult<ReportResponse>
+  where
+    C: ReportCriteria,
+  {
+    let request = ReportRequest::from_criteria(criteria, self.get_platform())?;
+    self.submit_report_request(request).await
+  }
+
   async fn get_report_status(
     &self,
     request_ids: &[&str],
     max_count: Option<u64>,
   ) -> NeweggResult<ReportResponse> {
     self
-      .request(Method::PUT, "/reportmgmt/report/status")
-      .json(&ReportRequest::new(
-        "GetReportStatusRequest",
-        GetReportStatusRequest::new(request_ids, max_count.unwrap_or(100)),
-      ))
-      .send()
+      .send(
+        self
+          .request(Method::PUT, "/reportmgmt/report/status")
+          .json(&ReportRequest::new(
+            "GetReportStatusRequest",
+            GetReportStatusRequest::new(request_ids, max_count.unwrap_or(100)),
+          )),
+      )
       .await?
       .get_response()
       .await
@@ -70,50 +133,245 @@ impl ReportApi for NeweggClient {
     page_size: Option<u64>,
   ) -> NeweggResult<ReportResultReponse> {
     self
-      .request(Method::PUT, "/reportmgmt/report/result")
-      .json(&ReportRequest::new(
-        operation_type,
-        GetReportResultRequest::new(request_id, page_index, page_size.unwrap_or(100)),
-      ))
-      .send()
+      .send(
+        self
+          .request(Method::PUT, "/reportmgmt/report/result")
+          .json(&ReportRequest::new(
+            operation_type,
+            GetReportResultRequest::new(request_id, page_index, page_size.unwrap_or(100)),
+          )),
+      )
       .await?
       .get_response()
       .await
   }
 
+  async fn get_report_page<C>(
+    &self,
+    criteria: &C,
+    request_id: &str,
+    page_index: u64,
+    page_size: Option<u64>,
+  ) -> NeweggResult<ReportPage<C::Row>>
+  where
+    C: ReportCriteria,
+  {
+    let kind = criteria.kind(self.get_platform());
+    let res: NeweggApiResponseWrapped<serde_json::Value> = self
+      .send(
+        self
+          .request(Method::PUT, "/reportmgmt/report/result")
+          .json(&ReportRequest::new(
+            kind.operation_type(),
+            GetReportResultRequest::new(request_id, page_index, page_size.unwrap_or(100)),
+          )),
+      )
+      .await?
+      .get_response()
+      .await?;
+    ReportPage::from_response_body(kind, res.newegg_api_response.response_body)
+  }
+
   async fn get_report_file(&self, url: &str) -> NeweggResult<Vec<u8>> {
-    use ftp::FtpStream;
-    use std::io::Read;
-    let url = url.to_owned();
-    block(move || {
-      let url = Url::parse(&url)?;
-      let host = url
-        .host_str()
-        .ok_or_else(|| NeweggError::FtpUrl(format!("no host")))?;
-      let port = url
-        .port_or_known_default()
-        .ok_or_else(|| NeweggError::FtpUrl(format!("no port")))?;
-      let mut stream = FtpStream::connect(&format!("{}:{}", host, port))?;
-      stream.login(
-        url.username(),
-        url
-          .password()
-          .ok_or_else(|| NeweggError::FtpUrl(format!("no password")))?,
-      )?;
-      let path_segments: Vec<_> = url
-        .path_segments()
-        .ok_or_else(|| NeweggError::FtpUrl(format!("no path")))?
-        .collect();
-      let mut r = if path_segments.len() > 1 {
-        let path: String = path_segments[..(path_segments.len() - 1)].join("");
-        stream.cwd(&path)?;
-        stream.get(path_segments[path_segments.len() - 1])?
-      } else {
-        stream.get(url.path())?
-      };
-      let mut data = vec![];
-      r.read_to_end(&mut data)?;
-      Ok(data)
-    }).await
+    let mut download = self.open_report_file(url, 0).await?;
+    let mut data = vec![];
+    download.read_to_end(&mut data).await?;
+    download.finish().await?;
+    Ok(data)
+  }
+
+  async fn open_report_file(&self, url: &str, offset: u64) -> NeweggResult<FileDownload> {
+    let url = TransferUrl::parse(url)?;
+    FileTransfer::connect(&url, self.transfer_options())
+      .await?
+      .download(&url.path, offset)
+      .await
+  }
+
+  async fn save_report_file(&self, url: &str, path: &Path) -> NeweggResult<u64> {
+    let mut file = tokio::fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)
+      .await?;
+    let offset = file.metadata().await?.len();
+    let transfer_url = TransferUrl::parse(url)?;
+    let mut transfer = FileTransfer::connect(&transfer_url, self.transfer_options()).await?;
+    if offset > 0 && offset >= transfer.size(&transfer_url.path).await? {
+      transfer.close().await?;
+      return Ok(offset);
+    }
+    let mut download = transfer.download(&transfer_url.path, offset).await?;
+    let size = tokio::io::copy(&mut download, &mut file).await?;
+    file.flush().await?;
+    download.finish().await?;
+    Ok(offset + size)
+  }
+
+  async fn wait_for_report(
+    &self,
+    request_id: &str,
+    options: RunReportOptions,
+  ) -> NeweggResult<ResponseInfo> {
+    poll_until_finished(Polled::Report, request_id, options.poll, || async move {
+      let info = self
+        .get_report_status(&[request_id], None)
+        .await?
+        .response_body
+        .response_list
+        .into_iter()
+        .find(|info| info.request_id == request_id);
+      Ok(info.and_then(|info| Some((info.request_status?, info))))
+    })
+    .await
+  }
+
+  fn run_report<'a, C>(
+    &'a self,
+    criteria: &'a C,
+    options: RunReportOptions,
+  ) -> BoxStream<'a, NeweggResult<C::Row>>
+  where
+    C: ReportCriteria,
+  {
+    enum Next {
+      Submit,
+      Page(String, u64),
+      Done,
+    }
+
+    struct State<T> {
+      next: Next,
+      buffer: VecDeque<T>,
+      file: Option<TempFileRows<T>>,
+    }
+
+    let state = State {
+      next: Next::Submit,
+      buffer: VecDeque::new(),
+      file: None,
+    };
+
+    stream::unfold(state, move |mut state| async move {
+      loop {
+        if let Some(row) = state.buffer.pop_front() {
+          return Some((Ok(row), state));
+        }
+        if let Some(file) = state.file.as_mut() {
+          match file.rows.next() {
+            Some(Ok(row)) => return Some((Ok(row), state)),
+            Some(Err(err)) => {
+              state.file = None;
+              return Some((Err(err), state));
+            }
+            None => {
+              state.file = None;
+              continue;
+            }
+          }
+        }
+
+        let (request_id, page_index) = match std::mem::replace(&mut state.next, Next::Done) {
+          Next::Done => return None,
+          Next::Page(request_id, page_index) => (request_id, page_index),
+          Next::Submit => match self.start_report(criteria, options).await {
+            Ok(Started::File(file)) => {
+              state.file = Some(file);
+              continue;
+            }
+            Ok(Started::Paged(request_id)) => (request_id, 1),
+            Err(err) => return Some((Err(err), state)),
+          },
+        };
+
+        let page = match self
+          .get_report_page(criteria, &request_id, page_index, Some(options.page_size))
+          .await
+        {
+          Ok(page) => page,
+          Err(err) => return Some((Err(err), state)),
+        };
+        let total_page_count = page
+          .page_info
+          .as_ref()
+          .map(|info| info.total_page_count)
+          .unwrap_or(0);
+        if !page.rows.is_empty() && (page_index as i64) < total_page_count {
+          state.next = Next::Page(request_id, page_index + 1);
+        }
+        state.buffer.extend(page.rows);
+      }
+    })
+    .boxed()
+  }
+}
+
+enum Started<T> {
+  /// Rows of the downloaded report file.
+  File(TempFileRows<T>),
+  /// Request id of a finished paged report.
+  Paged(String),
+}
+
+/// A report file in the temporary directory, deleted when dropped.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    std::fs::remove_file(&self.0).ok();
+  }
+}
+
+struct TempFileRows<T> {
+  rows: parse::FileRows<File, T>,
+  // dropped after `rows`, which holds the open file
+  _file: TempFile,
+}
+
+impl NeweggClient {
+  /// Submits a report and waits for it, file reports are downloaded to a
+  /// temporary file and opened for decoding.
+  async fn start_report<C>(&self, criteria: &C, options: RunReportOptions) -> NeweggResult<Started<C::Row>>
+  where
+    C: ReportCriteria,
+  {
+    let request_id = self
+      .submit_report(criteria)
+      .await?
+      .response_body
+      .response_list
+      .into_iter()
+      .next()
+      .map(|info| info.request_id)
+      .ok_or_else(|| NeweggError::Report("no request id in submit response".to_string()))?;
+    let info = self.wait_for_report(&request_id, options).await?;
+    if !criteria.kind(self.get_platform()).is_file_report() {
+      return Ok(Started::Paged(request_id));
+    }
+
+    let url = match info.report_file_url {
+      Some(url) => url,
+      None => self
+        .get_report_page(criteria, &request_id, 1, None)
+        .await?
+        .report_file_url
+        .ok_or_else(|| NeweggError::Report(format!("no report file url: {}", request_id)))?,
+    };
+    let file_type = criteria
+      .file_type()
+      .or_else(|| ReportFileType::from_url(&url))
+      .unwrap_or(ReportFileType::Csv);
+    let path = std::env::temp_dir().join(format!(
+      "newegg-{}-report-{}",
+      self.seller_id(),
+      request_id
+    ));
+    let temp_file = TempFile(path);
+    self.save_report_file(&url, &temp_file.0).await?;
+    let rows = parse::file_rows(File::open(&temp_file.0)?, file_type)?;
+    Ok(Started::File(TempFileRows {
+      rows,
+      _file: temp_file,
+    }))
   }
 }
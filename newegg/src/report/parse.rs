@@ -0,0 +1,215 @@
+//! Decoders for report files downloaded with `ReportApi::get_report_file`.
+//!
+//! Only the inventory and price reports are delivered as files, every other
+//! report comes as paged JSON results decoded by `ReportPage`. Their row types
+//! are `InventoryReportRow` (US), `CanInventoryReportRow` (Canada, Business)
+//! and `PriceReportRow`.
+//!
+//! Rows are matched by column name, so unknown extra columns are ignored.
+//! XLS files need the `xls` feature.
+
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Read};
+
+use super::ReportFileType;
+use crate::result::{NeweggError, NeweggResult};
+
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Iterator over the rows of a CSV or tab-delimited report file.
+pub struct ReportRows<R: Read, T> {
+  inner: csv::DeserializeRecordsIntoIter<BufReader<R>, T>,
+}
+
+impl<R, T> Iterator for ReportRows<R, T>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  type Item = NeweggResult<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(|row| row.map_err(NeweggError::from))
+  }
+}
+
+/// Streams the rows of a CSV report file.
+pub fn csv_rows<R, T>(reader: R) -> NeweggResult<ReportRows<R, T>>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  delimited_rows(reader, b',')
+}
+
+/// Streams the rows of a TXT (tab-delimited) report file.
+pub fn txt_rows<R, T>(reader: R) -> NeweggResult<ReportRows<R, T>>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  delimited_rows(reader, b'\t')
+}
+
+fn delimited_rows<R, T>(reader: R, delimiter: u8) -> NeweggResult<ReportRows<R, T>>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  let mut reader = BufReader::new(reader);
+  if reader.fill_buf()?.starts_with(BOM) {
+    reader.consume(BOM.len());
+  }
+  let inner = csv::ReaderBuilder::new()
+    .delimiter(delimiter)
+    .flexible(true)
+    .trim(csv::Trim::Headers)
+    .from_reader(reader)
+    .into_deserialize();
+  Ok(ReportRows { inner })
+}
+
+/// Decodes the first worksheet of an XLS/XLSX report file.
+///
+/// Workbooks can not be streamed, so the whole file has to be in memory.
+#[cfg(feature = "xls")]
+pub fn xls_rows<T>(data: &[u8]) -> NeweggResult<Vec<T>>
+where
+  T: for<'de> Deserialize<'de>,
+{
+  use calamine::Reader;
+
+  let mut workbook = calamine::open_workbook_auto_from_rs(std::io::Cursor::new(data))
+    .map_err(|err| NeweggError::Report(format!("open workbook: {}", err)))?;
+  let range = workbook
+    .worksheet_range_at(0)
+    .ok_or_else(|| NeweggError::Report("workbook has no worksheet".to_string()))?
+    .map_err(|err| NeweggError::Report(format!("read worksheet: {}", err)))?;
+
+  // re-encode as CSV so cells are decoded exactly like the other formats
+  let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+  for row in range.rows() {
+    writer.write_record(row.iter().map(|cell| cell.to_string()))?;
+  }
+  let csv = writer
+    .into_inner()
+    .map_err(|err| NeweggError::Report(format!("write csv: {}", err)))?;
+  csv_rows(&csv[..])?.collect()
+}
+
+/// Rows of a report file of any type, see `file_rows`.
+pub enum FileRows<R: Read, T> {
+  Delimited(ReportRows<R, T>),
+  Decoded(std::vec::IntoIter<T>),
+}
+
+impl<R, T> Iterator for FileRows<R, T>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  type Item = NeweggResult<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match *self {
+      FileRows::Delimited(ref mut rows) => rows.next(),
+      FileRows::Decoded(ref mut rows) => rows.next().map(Ok),
+    }
+  }
+}
+
+/// Streams the rows of a CSV or TXT report file read from `reader`.
+/// XLS files are read whole before the first row.
+pub fn file_rows<R, T>(mut reader: R, file_type: ReportFileType) -> NeweggResult<FileRows<R, T>>
+where
+  R: Read,
+  T: for<'de> Deserialize<'de>,
+{
+  match file_type {
+    ReportFileType::Csv => Ok(FileRows::Delimited(csv_rows(reader)?)),
+    ReportFileType::Txt => Ok(FileRows::Delimited(txt_rows(reader)?)),
+    ReportFileType::Xls => {
+      let mut data = vec![];
+      reader.read_to_end(&mut data)?;
+      Ok(FileRows::Decoded(parse_rows(&data, file_type)?.into_iter()))
+    }
+  }
+}
+
+/// Decodes a whole report file.
+pub fn parse_rows<T>(data: &[u8], file_type: ReportFileType) -> NeweggResult<Vec<T>>
+where
+  T: for<'de> Deserialize<'de>,
+{
+  match file_type {
+    ReportFileType::Csv => csv_rows(data)?.collect(),
+    ReportFileType::Txt => txt_rows(data)?.collect(),
+    #[cfg(feature = "xls")]
+    ReportFileType::Xls => xls_rows(data),
+    #[cfg(not(feature = "xls"))]
+    ReportFileType::Xls => Err(NeweggError::Report(
+      "XLS report files need the `xls` feature".to_string(),
+    )),
+  }
+}
+
+/// Decodes a report file downloaded from `url` (or a local path) as
+/// `file_type`, which is usually the type the report was requested with.
+/// Without it the type is guessed from the extension, defaulting to CSV.
+pub fn parse_report_file<T>(
+  data: &[u8],
+  file_type: Option<ReportFileType>,
+  url: &str,
+) -> NeweggResult<Vec<T>>
+where
+  T: for<'de> Deserialize<'de>,
+{
+  let file_type = file_type
+    .or_else(|| ReportFileType::from_url(url))
+    .unwrap_or(ReportFileType::Csv);
+  parse_rows(data, file_type)
+}
+
+#[test]
+fn test_parse_inventory_report_files() {
+  use super::{CanInventoryReportRow, InventoryReportRow};
+
+  let us = "\u{feff}Seller Part #,NE Item #,Warehouse Location,Fulfillment Option,Inventory,Extra\n\
+            edifier-r1280t,9SIA0000000001,USA,Seller,12,x\n";
+  let rows: Vec<InventoryReportRow> = parse_rows(us.as_bytes(), ReportFileType::Csv).unwrap();
+  assert_eq!(rows.len(), 1);
+  assert_eq!(rows[0].seller_part_number, "edifier-r1280t");
+  assert_eq!(rows[0].warehouse_location, "USA");
+  assert_eq!(rows[0].inventory, 12);
+
+  let ca = "Seller Part #\tNE Item #\tCurrency\tMSRP\tMAP\tCheckout MAP\tSelling Price\tInventory\tFulfillment option\tShipping\tActivation Mark\n\
+            edifier-r1280t\t9SIA0000000001\tCAD\t\t\tFalse\t159.99\t3\tSeller\tdefault\tTrue\n";
+  let rows: Vec<CanInventoryReportRow> =
+    parse_report_file(ca.as_bytes(), None, "ftp://ftp.newegg.com/Outbound/report.txt").unwrap();
+  assert_eq!(rows[0].inventory, 3);
+  assert_eq!(rows[0].fulfillment_option, "Seller");
+  assert_eq!(rows[0].currency, "CAD");
+  assert_eq!(rows[0].selling_price, "159.99".parse().unwrap());
+  assert!(rows[0].msrp.is_none());
+  assert!(rows[0].activation_mark);
+
+  // the common columns of a Canada file
+  let rows: Vec<InventoryReportRow> = parse_rows(ca.as_bytes(), ReportFileType::Txt).unwrap();
+  assert_eq!(rows[0].seller_part_number, "edifier-r1280t");
+  assert_eq!(rows[0].fulfillment_option, "Seller");
+  assert_eq!(rows[0].inventory, 3);
+
+  // the requested type wins over the extension
+  let rows: Vec<InventoryReportRow> =
+    parse_report_file(us.as_bytes(), Some(ReportFileType::Csv), "report.txt").unwrap();
+  assert_eq!(rows[0].inventory, 12);
+
+  let mut rows = file_rows::<_, InventoryReportRow>(
+    std::io::Cursor::new(format!("{}edifier-r1700bt,9SIA0000000002,USA,Seller,oops\n", us)),
+    ReportFileType::Csv,
+  )
+  .unwrap();
+  assert_eq!(rows.next().unwrap().unwrap().inventory, 12);
+  assert!(rows.next().unwrap().is_err());
+  assert!(rows.next().is_none());
+}
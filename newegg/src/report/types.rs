@@ -2,6 +2,7 @@ use serde::Serialize;
 use serde_derive::Deserialize;
 
 use crate::helpers::NeweggDateTime;
+pub use crate::poll::RequestStatus;
 use crate::types::{NeweggApiResponse, NeweggApiResponseWrapped};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,15 +58,6 @@ pub struct RequestIDList {
   request_id: Vec<String>,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RequestStatus {
-  Submitted,
-  InProgress,
-  Finished,
-  Cancelled,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct GetReportResultRequest {
@@ -1,17 +1,22 @@
 use thiserror::Error;
 use reqwest::StatusCode;
+use serde_derive::Deserialize;
 
 use crate::order::{CancelOrderResponse, ShipOrderResponse};
 
 #[derive(Error, Debug)]
 pub enum NeweggError {
+  /// Fields may be added, match with `..`
   #[error(
     "request error: path = '{path}', status = '{status}', body = '{body}'"
   )]
+  #[non_exhaustive]
   Request {
     path: String,
     status: StatusCode,
     body: String,
+    /// Decoded from `body`, empty if it is not a Newegg error payload
+    errors: Vec<NeweggApiError>,
   },
 
   #[error("deserialize body error: msg = '{msg}', body = '{body}'")]
@@ -35,30 +40,223 @@ pub enum NeweggError {
   #[error("parse url error: {0}")]
   Url(#[from] url::ParseError),
 
+  #[cfg(feature = "ftp")]
   #[error("ftp error: {0}")]
-  Ftp(#[from] ftp::types::FtpError),
+  Ftp(#[from] suppaftp::FtpError),
+
+  #[cfg(feature = "sftp")]
+  #[error("ssh error: {0}")]
+  Ssh(#[from] russh::Error),
+
+  #[cfg(feature = "sftp")]
+  #[error("sftp error: {0}")]
+  Sftp(#[from] russh_sftp::client::error::Error),
 
   #[error("ftp url error: {0}")]
   FtpUrl(String),
 
+  #[error("timed out connecting to file server: {0}")]
+  TransferTimeout(String),
+
+  #[error("xml error: {0}")]
+  Xml(String),
+
+  #[error("feed type can not be uploaded via ftp: {0}")]
+  FeedType(String),
+
+  #[error("report request has been cancelled: {0}")]
+  ReportCancelled(String),
+
+  #[error("timed out waiting for report: {0}")]
+  ReportTimeout(String),
+
+  #[error("unknown feed or report request status: {0}")]
+  UnknownRequestStatus(String),
+
+  #[error("feed error: {0}")]
+  Feed(String),
+
+  #[error("feed request has been cancelled: {0}")]
+  FeedCancelled(String),
+
+  #[error("timed out waiting for feed: {0}")]
+  FeedTimeout(String),
+
+  #[error("report error: {0}")]
+  Report(String),
+
+  #[error("no ISO 3166 code for country: {0}")]
+  UnknownCountry(String),
+
+  #[error("csv error: {0}")]
+  Csv(#[from] csv::Error),
+
   #[error("id error: {0}")]
   Io(#[from] std::io::Error),
 
+  #[error("base64 error: {0}")]
+  Base64(#[from] base64::DecodeError),
+
   #[error("runtime: {0}")]
   Runtime(#[from] tokio::task::JoinError),
+
+  #[error("config error: {0}")]
+  Config(#[from] crate::config::ConfigError),
+}
+
+impl From<quick_xml::Error> for NeweggError {
+  fn from(err: quick_xml::Error) -> Self {
+    NeweggError::Xml(err.to_string())
+  }
+}
+
+impl From<quick_xml::DeError> for NeweggError {
+  fn from(err: quick_xml::DeError) -> Self {
+    NeweggError::Xml(err.to_string())
+  }
+}
+
+impl From<quick_xml::SeError> for NeweggError {
+  fn from(err: quick_xml::SeError) -> Self {
+    NeweggError::Xml(err.to_string())
+  }
 }
 
 impl NeweggError {
+  /// Builds a `Request` error, decoding the Newegg error payload of `body`.
+  pub fn request(path: &str, status: StatusCode, body: &str) -> Self {
+    NeweggError::Request {
+      path: path.to_string(),
+      status,
+      body: body.to_string(),
+      errors: NeweggApiError::parse(body),
+    }
+  }
+
   pub fn should_try_again(&self) -> bool {
+    match self.api_error_kind() {
+      Some(NeweggApiErrorKind::Throttled) => true,
+      Some(NeweggApiErrorKind::Other) | None => match *self {
+        NeweggError::Request { status, .. } => is_retryable_status(status),
+        _ => false,
+      },
+      Some(_) => false,
+    }
+  }
+
+  pub fn api_errors(&self) -> &[NeweggApiError] {
     match *self {
-      NeweggError::Request { status, .. } => {
-        let code = status.as_u16();
-        // 429 Too Many Requests
-        code == 429 || code == 500 || code == 503
-      }
-      _ => false,
+      NeweggError::Request { ref errors, .. } => errors,
+      _ => &[],
+    }
+  }
+
+  /// The first recognized error code, or a kind implied by the status
+  /// (401/403 `AuthFailure`, 429 `Throttled`).
+  pub fn api_error_kind(&self) -> Option<NeweggApiErrorKind> {
+    let (status, errors) = match *self {
+      NeweggError::Request {
+        status, ref errors, ..
+      } => (status, errors),
+      _ => return None,
+    };
+    let kind = errors
+      .iter()
+      .map(NeweggApiError::kind)
+      .find(|kind| *kind != NeweggApiErrorKind::Other);
+    kind.or(match status.as_u16() {
+      401 | 403 => Some(NeweggApiErrorKind::AuthFailure),
+      429 => Some(NeweggApiErrorKind::Throttled),
+      _ if errors.is_empty() => None,
+      _ => Some(NeweggApiErrorKind::Other),
+    })
+  }
+}
+
+/// An entry of the error payload of failed requests,
+/// e.g. `[{"Code":"SO011","Message":"..."}]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct NeweggApiError {
+  #[serde(rename = "Code")]
+  pub code: String,
+  #[serde(rename = "Message", default)]
+  pub message: String,
+}
+
+impl NeweggApiError {
+  /// Decodes a list or a single error object, an empty list otherwise.
+  pub fn parse(body: &str) -> Vec<Self> {
+    match serde_json::from_str(body) {
+      Ok(crate::MaybeList::List(errors)) => errors,
+      Ok(crate::MaybeList::Single(error)) => vec![error],
+      Err(_) => vec![],
+    }
+  }
+
+  pub fn kind(&self) -> NeweggApiErrorKind {
+    NeweggApiErrorKind::from_code(&self.code)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeweggApiErrorKind {
+  /// `CE002`, invalid token or secret key
+  AuthFailure,
+  /// `CE001`, invalid seller id or the seller has not authorized the token
+  InvalidSeller,
+  /// `429 Too Many Requests`
+  Throttled,
+  /// `SO002`
+  OrderNotFound,
+  /// `SO011`/`SO012`, e.g. cancelling a shipped order
+  InvalidStatusTransition,
+  Other,
+}
+
+impl NeweggApiErrorKind {
+  pub fn from_code(code: &str) -> Self {
+    match code.trim().to_uppercase().as_ref() {
+      "CE001" => NeweggApiErrorKind::InvalidSeller,
+      "CE002" => NeweggApiErrorKind::AuthFailure,
+      "SO002" => NeweggApiErrorKind::OrderNotFound,
+      "SO011" | "SO012" => NeweggApiErrorKind::InvalidStatusTransition,
+      _ => NeweggApiErrorKind::Other,
     }
   }
 }
 
-pub type NeweggResult<T> = ::std::result::Result<T, NeweggError>;
\ No newline at end of file
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+  let code = status.as_u16();
+  // 429 Too Many Requests
+  code == 429 || code == 500 || code == 503
+}
+
+pub type NeweggResult<T> = ::std::result::Result<T, NeweggError>;
+
+#[test]
+fn test_api_error_kind() {
+  let err = NeweggError::request(
+    "/ordermgmt/orderstatus/orders/1001",
+    StatusCode::BAD_REQUEST,
+    r#"[{"Code":"SO011","Message":"Only unshipped orders can be cancelled."}]"#,
+  );
+  assert_eq!(err.api_errors()[0].code, "SO011");
+  assert_eq!(
+    err.api_error_kind(),
+    Some(NeweggApiErrorKind::InvalidStatusTransition)
+  );
+  assert!(!err.should_try_again());
+
+  let err = NeweggError::request("/", StatusCode::TOO_MANY_REQUESTS, "Too Many Requests");
+  assert!(err.api_errors().is_empty());
+  assert_eq!(err.api_error_kind(), Some(NeweggApiErrorKind::Throttled));
+  assert!(err.should_try_again());
+
+  let err = NeweggError::request(
+    "/",
+    StatusCode::SERVICE_UNAVAILABLE,
+    r#"{"Code":"CE999","Message":"Service unavailable."}"#,
+  );
+  assert_eq!(err.api_error_kind(), Some(NeweggApiErrorKind::Other));
+  assert!(err.should_try_again());
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+use crate::result::{NeweggApiErrorKind, NeweggError};
+
+/// Controls how `NeweggClient` replays requests whose error is classified by
+/// `NeweggError::should_try_again`: throttled requests and `500`/`503`
+/// responses without a more specific error code.
+///
+/// Throttled requests (`429 Too Many Requests`) were never processed by
+/// Newegg and are always safe to replay. `500`/`503` responses to
+/// non-idempotent requests (e.g. `ship_order`, `cancel_order`, `submit_feed`)
+/// might have been applied, so they are only replayed if
+/// `retry_non_idempotent` is set.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+  /// Total number of attempts, including the first one.
+  pub max_attempts: u32,
+  /// Delay before the first retry, doubled on every following attempt.
+  pub base_delay: Duration,
+  /// Upper bound of the computed backoff delay. A longer `Retry-After`
+  /// returns the response instead of waiting.
+  pub max_delay: Duration,
+  /// Replay non-idempotent requests on 500/503 as well.
+  pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    RetryPolicy {
+      max_attempts: 3,
+      base_delay: Duration::from_secs(1),
+      max_delay: Duration::from_secs(60),
+      retry_non_idempotent: false,
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// A policy that never retries.
+  pub fn none() -> Self {
+    RetryPolicy {
+      max_attempts: 1,
+      ..Default::default()
+    }
+  }
+
+  pub fn max_attempts(self, v: u32) -> Self {
+    Self {
+      max_attempts: v,
+      ..self
+    }
+  }
+
+  pub fn base_delay(self, v: Duration) -> Self {
+    Self {
+      base_delay: v,
+      ..self
+    }
+  }
+
+  pub fn max_delay(self, v: Duration) -> Self {
+    Self {
+      max_delay: v,
+      ..self
+    }
+  }
+
+  pub fn retry_non_idempotent(self, v: bool) -> Self {
+    Self {
+      retry_non_idempotent: v,
+      ..self
+    }
+  }
+
+  /// Returns the delay before the next attempt, or `None` if `err` should be
+  /// returned to the caller.
+  ///
+  /// `attempt` is the 1-based number of the attempt that failed with `err`,
+  /// `headers` are the headers of its response. A `Retry-After` longer than
+  /// `max_delay` gives up instead of blocking the caller for that long.
+  pub(crate) fn next_delay(
+    &self,
+    attempt: u32,
+    err: &NeweggError,
+    headers: &HeaderMap,
+    idempotent: bool,
+  ) -> Option<Duration> {
+    if attempt >= self.max_attempts || !err.should_try_again() {
+      return None;
+    }
+
+    let throttled = err.api_error_kind() == Some(NeweggApiErrorKind::Throttled);
+    if !throttled && !idempotent && !self.retry_non_idempotent {
+      return None;
+    }
+
+    match retry_after(headers) {
+      Some(delay) if delay > self.max_delay => None,
+      Some(delay) => Some(delay),
+      None => Some(self.backoff(attempt)),
+    }
+  }
+
+  /// Exponential backoff with full jitter.
+  fn backoff(&self, attempt: u32) -> Duration {
+    let exp = self
+      .base_delay
+      .checked_mul(1 << (attempt - 1).min(16))
+      .unwrap_or(self.max_delay)
+      .min(self.max_delay);
+    exp.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+  }
+}
+
+/// Parses the `Retry-After` header, either delay-seconds or an HTTP-date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+  let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+  if let Ok(secs) = value.parse::<u64>() {
+    return Some(Duration::from_secs(secs));
+  }
+  let date = DateTime::parse_from_rfc2822(value).ok()?;
+  (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+#[test]
+fn test_backoff_is_capped() {
+  let policy = RetryPolicy::default()
+    .base_delay(Duration::from_secs(2))
+    .max_delay(Duration::from_secs(5));
+  for attempt in 1..40 {
+    assert!(policy.backoff(attempt) <= Duration::from_secs(5));
+  }
+  assert!(policy.backoff(1) <= Duration::from_secs(2));
+}
+
+#[cfg(test)]
+fn status_error(status: reqwest::StatusCode, body: &str) -> NeweggError {
+  NeweggError::request("/ordermgmt/order/orderinfo", status, body)
+}
+
+#[test]
+fn test_retry_after() {
+  use reqwest::StatusCode;
+  let policy = RetryPolicy::default().max_delay(Duration::from_secs(60));
+  let throttled = status_error(StatusCode::TOO_MANY_REQUESTS, "");
+  let mut headers = HeaderMap::new();
+  headers.insert(RETRY_AFTER, reqwest::header::HeaderValue::from_static("30"));
+  assert_eq!(
+    policy.next_delay(1, &throttled, &headers, true),
+    Some(Duration::from_secs(30))
+  );
+  headers.insert(RETRY_AFTER, reqwest::header::HeaderValue::from_static("3600"));
+  assert_eq!(policy.next_delay(1, &throttled, &headers, true), None);
+  headers.insert(RETRY_AFTER, reqwest::header::HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+  assert_eq!(retry_after(&headers), None);
+}
+
+#[test]
+fn test_retry_idempotency() {
+  use reqwest::StatusCode;
+  let policy = RetryPolicy::default();
+  let headers = HeaderMap::new();
+  let delay =
+    |status, idempotent| policy.next_delay(1, &status_error(status, ""), &headers, idempotent);
+  assert!(delay(StatusCode::SERVICE_UNAVAILABLE, true).is_some());
+  assert!(delay(StatusCode::SERVICE_UNAVAILABLE, false).is_none());
+  assert!(delay(StatusCode::TOO_MANY_REQUESTS, false).is_some());
+  assert!(delay(StatusCode::BAD_REQUEST, true).is_none());
+  assert!(policy
+    .retry_non_idempotent(true)
+    .next_delay(1, &status_error(StatusCode::INTERNAL_SERVER_ERROR, ""), &headers, false)
+    .is_some());
+  assert!(RetryPolicy::default()
+    .next_delay(3, &status_error(StatusCode::SERVICE_UNAVAILABLE, ""), &headers, true)
+    .is_none());
+}
+
+#[test]
+fn test_retry_error_codes() {
+  use reqwest::StatusCode;
+  let policy = RetryPolicy::default();
+  let headers = HeaderMap::new();
+  // a failed authorization is not retried, whatever the status
+  let err = status_error(
+    StatusCode::SERVICE_UNAVAILABLE,
+    r#"[{"Code":"CE002","Message":"Unauthorized."}]"#,
+  );
+  assert_eq!(policy.next_delay(1, &err, &headers, true), None);
+  let err = status_error(
+    StatusCode::SERVICE_UNAVAILABLE,
+    r#"[{"Code":"CE999","Message":"Service unavailable."}]"#,
+  );
+  assert!(policy.next_delay(1, &err, &headers, true).is_some());
+}
@@ -0,0 +1,80 @@
+use crate::client::*;
+use crate::result::NeweggResult;
+use crate::types::NeweggApiRequest;
+use async_trait::async_trait;
+
+mod types;
+
+pub use self::types::*;
+
+#[async_trait]
+pub trait RmaApi {
+  async fn submit_rma(&self, request: &SubmitRmaRequest) -> NeweggResult<SubmitRmaResponse>;
+  async fn update_rma(&self, request: &UpdateRmaRequest) -> NeweggResult<UpdateRmaResponse>;
+  async fn cancel_rma(&self, rma_number: i64) -> NeweggResult<UpdateRmaResponse>;
+  async fn get_rma_info(&self, request: &GetRmaInfoRequest) -> NeweggResult<GetRmaInfoResponse>;
+  async fn issue_courtesy_refund(
+    &self,
+    request: &IssueCourtesyRefundRequest,
+  ) -> NeweggResult<IssueCourtesyRefundResponse>;
+}
+
+#[async_trait]
+impl RmaApi for NeweggClient {
+  async fn submit_rma(&self, request: &SubmitRmaRequest) -> NeweggResult<SubmitRmaResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::POST, "/servicemgmt/rma/newrma")
+          .json(&NeweggApiRequest::new("IssueRMARequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn update_rma(&self, request: &UpdateRmaRequest) -> NeweggResult<UpdateRmaResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::PUT, "/servicemgmt/rma/updatestatus")
+          .json(&NeweggApiRequest::new("EditRMARequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn cancel_rma(&self, rma_number: i64) -> NeweggResult<UpdateRmaResponse> {
+    self
+      .update_rma(&UpdateRmaRequest::new(rma_number, RmaUpdateAction::Void))
+      .await
+  }
+
+  async fn get_rma_info(&self, request: &GetRmaInfoRequest) -> NeweggResult<GetRmaInfoResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/servicemgmt/rma/rmainfo")
+          .json(request),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn issue_courtesy_refund(
+    &self,
+    request: &IssueCourtesyRefundRequest,
+  ) -> NeweggResult<IssueCourtesyRefundResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::POST, "/servicemgmt/courtesyrefund/new")
+          .json(&NeweggApiRequest::new("IssueCourtesyRefundRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+}
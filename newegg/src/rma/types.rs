@@ -0,0 +1,505 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::helpers::NeweggDateTime;
+use crate::order::PageInfo;
+use crate::types::NeweggApiResponse;
+
+enum_number! {
+  RmaType {
+    Refund = 1,
+    Replacement = 2,
+  }
+}
+
+enum_number! {
+  RmaStatus {
+    Open = 1,
+    Received = 2,
+    Rejected = 3,
+    Voided = 4,
+    Closed = 5,
+  }
+}
+
+enum_number! {
+  RmaProcessedBy {
+    All = 0,
+    Newegg = 1,
+    Seller = 2,
+  }
+}
+
+enum_number! {
+  RmaUpdateAction {
+    Edit = 1,
+    Receive = 2,
+    Reject = 3,
+    Void = 4,
+  }
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/rma_management/submit_rma/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitRmaRequest {
+  #[serde(rename = "RMAInfo")]
+  pub rma_info: SubmitRmaInfo,
+}
+
+impl SubmitRmaRequest {
+  pub fn new(order_number: i64, rma_type: RmaType) -> SubmitRmaRequestBuilder {
+    SubmitRmaRequestBuilder {
+      inner: SubmitRmaInfo {
+        source_so_number: order_number,
+        rma_type,
+        seller_rma_number: None,
+        rma_note: None,
+        rma_transaction_list: RmaTransactionList {
+          rma_transaction: vec![],
+        },
+      },
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmitRmaInfo {
+  #[serde(rename = "SourceSONumber")]
+  pub source_so_number: i64,
+  #[serde(rename = "RMAType")]
+  pub rma_type: RmaType,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellerRMANumber")]
+  pub seller_rma_number: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMANote")]
+  pub rma_note: Option<String>,
+  #[serde(rename = "RMATransactionList")]
+  pub rma_transaction_list: RmaTransactionList,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RmaTransactionList {
+  #[serde(rename = "RMATransaction")]
+  pub rma_transaction: Vec<RmaTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RmaTransaction {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "ReturnQuantity")]
+  pub return_quantity: i32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ReturnUnitPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub return_unit_price: Option<BigDecimal>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RefundShippingPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub refund_shipping_price: Option<BigDecimal>,
+  #[serde(rename = "RMAReason")]
+  pub rma_reason: i32,
+}
+
+pub struct SubmitRmaRequestBuilder {
+  inner: SubmitRmaInfo,
+}
+
+impl SubmitRmaRequestBuilder {
+  pub fn seller_rma_number(&mut self, v: &str) -> &mut Self {
+    self.inner.seller_rma_number = Some(v.to_owned());
+    self
+  }
+
+  pub fn rma_note(&mut self, v: &str) -> &mut Self {
+    self.inner.rma_note = Some(v.to_owned());
+    self
+  }
+
+  pub fn add_transaction(&mut self, transaction: RmaTransaction) -> &mut Self {
+    self.inner.rma_transaction_list.rma_transaction.push(transaction);
+    self
+  }
+
+  pub fn finalize(&mut self) -> SubmitRmaRequest {
+    let replace = SubmitRmaRequest::new(self.inner.source_so_number, self.inner.rma_type).inner;
+    SubmitRmaRequest {
+      rma_info: ::std::mem::replace(&mut self.inner, replace),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitRmaResponseBody {
+  #[serde(rename = "RMAInfo")]
+  pub rma_info: SubmittedRmaInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmittedRmaInfo {
+  #[serde(rename = "RMANumber")]
+  pub rma_number: i64,
+  #[serde(rename = "RMAType")]
+  pub rma_type: RmaType,
+  #[serde(rename = "SourceSONumber")]
+  pub source_so_number: i64,
+  #[serde(rename = "SellerRMANumber")]
+  pub seller_rma_number: Option<String>,
+  #[serde(rename = "IssueUser")]
+  pub issue_user: Option<String>,
+  #[serde(rename = "RMATransactionList")]
+  pub rma_transaction_list: Option<RmaTransactionList>,
+}
+
+pub type SubmitRmaResponse = NeweggApiResponse<SubmitRmaResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/rma_management/update_rma/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateRmaRequest {
+  #[serde(rename = "RMANumber")]
+  pub rma_number: i64,
+  #[serde(rename = "Action")]
+  pub action: RmaUpdateAction,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "EditRMAInfo")]
+  pub edit_rma_info: Option<EditRmaInfo>,
+}
+
+impl UpdateRmaRequest {
+  pub fn new(rma_number: i64, action: RmaUpdateAction) -> Self {
+    UpdateRmaRequest {
+      rma_number,
+      action,
+      edit_rma_info: None,
+    }
+  }
+
+  pub fn edit(rma_number: i64, info: EditRmaInfo) -> Self {
+    UpdateRmaRequest {
+      rma_number,
+      action: RmaUpdateAction::Edit,
+      edit_rma_info: Some(info),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditRmaInfo {
+  #[serde(rename = "RMAType")]
+  pub rma_type: RmaType,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellerRMANumber")]
+  pub seller_rma_number: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMANote")]
+  pub rma_note: Option<String>,
+  #[serde(rename = "RMATransactionList")]
+  pub rma_transaction_list: RmaTransactionList,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateRmaResponseBody {
+  #[serde(rename = "RMANumber")]
+  pub rma_number: i64,
+  #[serde(rename = "RMAStatus")]
+  pub rma_status: Option<RmaStatus>,
+  #[serde(rename = "RMAStatusDescription")]
+  pub rma_status_description: Option<String>,
+}
+
+pub type UpdateRmaResponse = NeweggApiResponse<UpdateRmaResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/rma_management/get_rma_info/
+#[derive(Default, Clone)]
+pub struct GetRmaInfoRequest {
+  pub request_body: GetRmaInfoRequestBody,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct GetRmaInfoRequestBody {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageIndex")]
+  pub page_index: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageSize")]
+  pub page_size: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "KeywordsType")]
+  pub keywords_type: Option<RmaKeywordsType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "KeywordsValue")]
+  pub keywords_value: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Status")]
+  pub status: Option<RmaStatus>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Type")]
+  pub type_: Option<RmaType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMADateFrom")]
+  pub rma_date_from: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "RMADateTo")]
+  pub rma_date_to: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ProcessedBy")]
+  pub processed_by: Option<RmaProcessedBy>,
+}
+
+enum_number! {
+  RmaKeywordsType {
+    RmaNumber = 1,
+    OrderNumber = 2,
+    SellerRmaNumber = 3,
+    SellerPartNumber = 4,
+  }
+}
+
+pub struct GetRmaInfoRequestBuilder {
+  inner: GetRmaInfoRequest,
+}
+
+impl GetRmaInfoRequestBuilder {
+  pub fn finalize(&mut self) -> GetRmaInfoRequest {
+    let mut req = ::std::mem::take(&mut self.inner);
+    if req.request_body.page_size.is_none() {
+      req.request_body.page_size = Some(100);
+    }
+    req
+  }
+
+  pub fn page_index(&mut self, v: i32) -> &mut Self {
+    self.inner.request_body.page_index = Some(v);
+    self
+  }
+
+  pub fn page_size(&mut self, v: i32) -> &mut Self {
+    self.inner.request_body.page_size = Some(v);
+    self
+  }
+
+  pub fn keywords(&mut self, type_: RmaKeywordsType, value: &str) -> &mut Self {
+    self.inner.request_body.keywords_type = Some(type_);
+    self.inner.request_body.keywords_value = Some(value.to_owned());
+    self
+  }
+
+  pub fn rma_status(&mut self, v: RmaStatus) -> &mut Self {
+    self.inner.request_body.status = Some(v);
+    self
+  }
+
+  pub fn rma_type(&mut self, v: RmaType) -> &mut Self {
+    self.inner.request_body.type_ = Some(v);
+    self
+  }
+
+  pub fn rma_date_from(&mut self, v: DateTime<Utc>) -> &mut Self {
+    self.inner.request_body.rma_date_from = Some(NeweggDateTime::from_utc(v));
+    self
+  }
+
+  pub fn rma_date_to(&mut self, v: DateTime<Utc>) -> &mut Self {
+    self.inner.request_body.rma_date_to = Some(NeweggDateTime::from_utc(v));
+    self
+  }
+
+  pub fn processed_by(&mut self, v: RmaProcessedBy) -> &mut Self {
+    self.inner.request_body.processed_by = Some(v);
+    self
+  }
+}
+
+impl GetRmaInfoRequest {
+  pub fn new() -> GetRmaInfoRequestBuilder {
+    GetRmaInfoRequestBuilder {
+      inner: GetRmaInfoRequest::default(),
+    }
+  }
+}
+
+impl Serialize for GetRmaInfoRequest {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state = serializer.serialize_struct("GetRmaInfoRequest", 2)?;
+    state.serialize_field("OperationType", "GetRMAInfoRequest")?;
+    state.serialize_field("RequestBody", &self.request_body)?;
+    state.end()
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRmaInfoResponseBody {
+  #[serde(rename = "PageInfo")]
+  pub page_info: PageInfo,
+  #[serde(rename = "RMAInfoList")]
+  pub rma_info_list: Option<Vec<RmaInfo>>,
+}
+
+pub type GetRmaInfoResponse = NeweggApiResponse<GetRmaInfoResponseBody>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RmaInfo {
+  #[serde(rename = "RMANumber")]
+  pub rma_number: i64,
+  #[serde(rename = "RMAType")]
+  pub rma_type: RmaType,
+  #[serde(rename = "RMATypeDescription")]
+  pub rma_type_description: String,
+  #[serde(rename = "SourceSONumber")]
+  pub source_so_number: i64,
+  #[serde(rename = "SellerOrderNumber")]
+  pub seller_order_number: Option<String>,
+  #[serde(rename = "RMADate")]
+  pub rma_date: NeweggDateTime,
+  #[serde(rename = "RMAStatus")]
+  pub rma_status: RmaStatus,
+  #[serde(rename = "RMAStatusDescription")]
+  pub rma_status_description: String,
+  #[serde(rename = "RMAProcessedBy")]
+  pub rma_processed_by: Option<String>,
+  #[serde(rename = "SellerRMANumber")]
+  pub seller_rma_number: Option<String>,
+  #[serde(rename = "RMANote")]
+  pub rma_note: Option<String>,
+  #[serde(rename = "CustomerName")]
+  pub customer_name: Option<String>,
+  #[serde(rename = "CustomerPhoneNumber")]
+  pub customer_phone_number: Option<String>,
+  #[serde(rename = "CustomerEmailAddress")]
+  pub customer_email_address: Option<String>,
+  #[serde(rename = "RMATransactionList")]
+  #[serde(default)]
+  pub rma_transaction_list: Vec<RmaInfoTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RmaInfoTransaction {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "MfrPartNumber")]
+  pub mfr_part_number: Option<String>,
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(rename = "Description")]
+  pub description: Option<String>,
+  #[serde(rename = "UnitPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub unit_price: Option<BigDecimal>,
+  #[serde(rename = "ReturnQuantity")]
+  pub return_quantity: i32,
+  #[serde(rename = "ReturnUnitPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub return_unit_price: Option<BigDecimal>,
+  #[serde(rename = "RefundShippingPrice")]
+  #[serde(default, with = "crate::helpers::option_decimal")]
+  pub refund_shipping_price: Option<BigDecimal>,
+  #[serde(rename = "RMAReason")]
+  pub rma_reason: Option<i32>,
+  #[serde(rename = "RMAReasonDescription")]
+  pub rma_reason_description: Option<String>,
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/rma_management/issue_courtesy_refund/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueCourtesyRefundRequest {
+  #[serde(rename = "CourtesyRefund")]
+  pub courtesy_refund: CourtesyRefund,
+}
+
+impl IssueCourtesyRefundRequest {
+  pub fn new(order_number: i64, reason: i32, amount: BigDecimal) -> Self {
+    IssueCourtesyRefundRequest {
+      courtesy_refund: CourtesyRefund {
+        so_number: order_number,
+        refund_reason: reason,
+        total_refund_amount: amount,
+        note_to_customer: None,
+      },
+    }
+  }
+
+  pub fn note_to_customer(mut self, v: &str) -> Self {
+    self.courtesy_refund.note_to_customer = Some(v.to_owned());
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CourtesyRefund {
+  #[serde(rename = "SONumber")]
+  pub so_number: i64,
+  #[serde(rename = "RefundReason")]
+  pub refund_reason: i32,
+  #[serde(rename = "TotalRefundAmount")]
+  #[serde(with = "crate::helpers::decimal")]
+  pub total_refund_amount: BigDecimal,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "NoteToCustomer")]
+  pub note_to_customer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueCourtesyRefundResponseBody {
+  #[serde(rename = "CourtesyRefundID")]
+  pub courtesy_refund_id: String,
+  #[serde(rename = "SONumber")]
+  pub so_number: i64,
+  #[serde(rename = "TotalRefundAmount")]
+  #[serde(with = "crate::helpers::decimal")]
+  pub total_refund_amount: BigDecimal,
+  #[serde(rename = "Status")]
+  pub status: Option<String>,
+}
+
+pub type IssueCourtesyRefundResponse = NeweggApiResponse<IssueCourtesyRefundResponseBody>;
+
+#[test]
+fn test_rma_types() {
+  use serde_json::json;
+
+  let req = GetRmaInfoRequest::new()
+    .keywords(RmaKeywordsType::OrderNumber, "1001")
+    .rma_status(RmaStatus::Open)
+    .finalize();
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    json!({
+      "OperationType": "GetRMAInfoRequest",
+      "RequestBody": { "PageSize": 100, "KeywordsType": 2, "KeywordsValue": "1001", "Status": 1 }
+    })
+  );
+
+  let req = IssueCourtesyRefundRequest::new(1001, 1, "12.30".parse().unwrap());
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    json!({ "CourtesyRefund": { "SONumber": 1001, "RefundReason": 1, "TotalRefundAmount": 12.3 } })
+  );
+
+  let info: RmaInfo = serde_json::from_value(json!({
+    "RMANumber": 21000001,
+    "RMAType": 1,
+    "RMATypeDescription": "Refund",
+    "SourceSONumber": 1001,
+    "RMADate": "02/12/2018 12:16:43",
+    "RMAStatus": 2,
+    "RMAStatusDescription": "Received",
+    "RMATransactionList": [{
+      "SellerPartNumber": "sku-1",
+      "UnitPrice": 19.99,
+      "ReturnQuantity": 1,
+      "ReturnUnitPrice": 19.99,
+      "RefundShippingPrice": 0
+    }]
+  }))
+  .unwrap();
+  assert_eq!(info.rma_status, RmaStatus::Received);
+  let transaction = &info.rma_transaction_list[0];
+  assert_eq!(transaction.return_unit_price.as_ref().unwrap().to_string(), "19.99");
+  assert_eq!(transaction.refund_shipping_price, Some(BigDecimal::from(0)));
+}
@@ -0,0 +1,93 @@
+use crate::client::*;
+use crate::result::NeweggResult;
+use crate::types::NeweggApiRequest;
+use async_trait::async_trait;
+use serde_json::json;
+
+mod types;
+
+pub use self::types::*;
+
+/// Shipped by Newegg (SBN) inbound shipments.
+#[async_trait]
+pub trait SbnApi {
+  async fn submit_inbound_shipment(
+    &self,
+    plan: &InboundShipmentPlan,
+  ) -> NeweggResult<SubmitInboundShipmentResponse>;
+  async fn get_inbound_shipment_status(
+    &self,
+    request_id: &str,
+  ) -> NeweggResult<GetInboundShipmentStatusResponse>;
+  async fn get_inbound_shipment_list(
+    &self,
+    request: &GetInboundShipmentListRequest,
+  ) -> NeweggResult<GetInboundShipmentListResponse>;
+  async fn get_package_label(&self, shipment_id: &str) -> NeweggResult<GetPackageLabelResponse>;
+}
+
+#[async_trait]
+impl SbnApi for NeweggClient {
+  async fn submit_inbound_shipment(
+    &self,
+    plan: &InboundShipmentPlan,
+  ) -> NeweggResult<SubmitInboundShipmentResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::POST, "/sbnmgmt/inboundshipment/shipmentrequest")
+          .json(&NeweggApiRequest::new("SubmitInboundShipmentRequest", plan)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_inbound_shipment_status(
+    &self,
+    request_id: &str,
+  ) -> NeweggResult<GetInboundShipmentStatusResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sbnmgmt/inboundshipment/shipmentstatus")
+          .json(&NeweggApiRequest::new(
+            "GetInboundShipmentRequestStatus",
+            json!({ "RequestID": request_id }),
+          )),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_inbound_shipment_list(
+    &self,
+    request: &GetInboundShipmentListRequest,
+  ) -> NeweggResult<GetInboundShipmentListResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sbnmgmt/inboundshipment/shipmentlist")
+          .json(&NeweggApiRequest::new("GetInboundShipmentListRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_package_label(&self, shipment_id: &str) -> NeweggResult<GetPackageLabelResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sbnmgmt/inboundshipment/shippinglabel")
+          .json(&NeweggApiRequest::new(
+            "GetShippingLabelRequest",
+            json!({ "ShipmentID": shipment_id }),
+          )),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+}
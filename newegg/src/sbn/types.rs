@@ -0,0 +1,404 @@
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::feed::RequestStatus;
+use crate::helpers::NeweggDateTime;
+use crate::order::PageInfo;
+use crate::result::NeweggResult;
+use crate::types::{MaybeList, NeweggApiResponse};
+
+enum_number! {
+  InboundShippingMethod {
+    SmallParcel = 1,
+    Ltl = 2,
+  }
+}
+
+enum_number! {
+  InboundShipmentStatus {
+    Created = 0,
+    Shipped = 1,
+    InTransit = 2,
+    Received = 3,
+    Closed = 4,
+    Cancelled = 5,
+  }
+}
+
+/// https://developer.newegg.com/newegg_marketplace_api/sbn_management/submit_inbound_shipment_request/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundShipmentPlan {
+  #[serde(rename = "Shipment")]
+  pub shipment: InboundShipment,
+}
+
+impl InboundShipmentPlan {
+  pub fn new(
+    ship_to_warehouse: &str,
+    shipping_method: InboundShippingMethod,
+  ) -> InboundShipmentPlanBuilder {
+    InboundShipmentPlanBuilder {
+      inner: InboundShipment::new(ship_to_warehouse, shipping_method),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundShipment {
+  #[serde(rename = "Header")]
+  pub header: InboundShipmentHeader,
+  #[serde(rename = "ItemList")]
+  pub item_list: InboundItemList,
+}
+
+impl InboundShipment {
+  fn new(ship_to_warehouse: &str, shipping_method: InboundShippingMethod) -> Self {
+    InboundShipment {
+      header: InboundShipmentHeader {
+        ship_to_warehouse: ship_to_warehouse.to_owned(),
+        shipping_method,
+        shipping_carrier: None,
+        ship_date: None,
+        seller_shipment_number: None,
+      },
+      item_list: InboundItemList { item: vec![] },
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundShipmentHeader {
+  #[serde(rename = "ShipToWarehouse")]
+  pub ship_to_warehouse: String,
+  #[serde(rename = "ShippingMethod")]
+  pub shipping_method: InboundShippingMethod,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShippingCarrier")]
+  pub shipping_carrier: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShipDate")]
+  pub ship_date: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SellerShipmentNumber")]
+  pub seller_shipment_number: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundItemList {
+  #[serde(rename = "Item")]
+  pub item: Vec<InboundShipmentItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InboundShipmentItem {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(rename = "ItemQuantity")]
+  pub item_quantity: i32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PackageQuantity")]
+  pub package_quantity: Option<i32>,
+}
+
+pub struct InboundShipmentPlanBuilder {
+  inner: InboundShipment,
+}
+
+impl InboundShipmentPlanBuilder {
+  pub fn shipping_carrier(&mut self, v: &str) -> &mut Self {
+    self.inner.header.shipping_carrier = Some(v.to_owned());
+    self
+  }
+
+  pub fn ship_date(&mut self, v: DateTime<Utc>) -> &mut Self {
+    self.inner.header.ship_date = Some(NeweggDateTime::from_utc(v));
+    self
+  }
+
+  pub fn seller_shipment_number(&mut self, v: &str) -> &mut Self {
+    self.inner.header.seller_shipment_number = Some(v.to_owned());
+    self
+  }
+
+  pub fn add_item(&mut self, seller_part_number: &str, qty: i32) -> &mut Self {
+    self.inner.item_list.item.push(InboundShipmentItem {
+      seller_part_number: seller_part_number.to_owned(),
+      item_quantity: qty,
+      ..Default::default()
+    });
+    self
+  }
+
+  pub fn finalize(&mut self) -> InboundShipmentPlan {
+    let replace = InboundShipment::new(
+      &self.inner.header.ship_to_warehouse,
+      self.inner.header.shipping_method,
+    );
+    InboundShipmentPlan {
+      shipment: ::std::mem::replace(&mut self.inner, replace),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboundShipmentRequestResponseBody {
+  #[serde(rename = "ResponseList")]
+  pub response_list: Vec<InboundShipmentRequestInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboundShipmentRequestInfo {
+  #[serde(rename = "RequestID")]
+  pub request_id: String,
+  #[serde(rename = "RequestDate")]
+  pub request_date: Option<NeweggDateTime>,
+  #[serde(rename = "RequestStatus")]
+  pub request_status: Option<RequestStatus>,
+  #[serde(rename = "ShipmentID")]
+  pub shipment_id: Option<String>,
+  #[serde(rename = "ErrorList")]
+  pub error_list: Option<InboundShipmentErrorList>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboundShipmentErrorList {
+  #[serde(rename = "ErrorDescription")]
+  pub error_description: MaybeList<String>,
+}
+
+pub type SubmitInboundShipmentResponse = NeweggApiResponse<InboundShipmentRequestResponseBody>;
+pub type GetInboundShipmentStatusResponse = NeweggApiResponse<InboundShipmentRequestResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/sbn_management/get_inbound_shipment_list/
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct GetInboundShipmentListRequest {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageIndex")]
+  pub page_index: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageSize")]
+  pub page_size: Option<i32>,
+  #[serde(rename = "RequestCriteria")]
+  pub request_criteria: InboundShipmentCriteria,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct InboundShipmentCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShipmentIDList")]
+  pub shipment_id_list: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShipmentStatus")]
+  pub shipment_status: Option<InboundShipmentStatus>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CreateDateFrom")]
+  pub create_date_from: Option<NeweggDateTime>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CreateDateTo")]
+  pub create_date_to: Option<NeweggDateTime>,
+}
+
+pub struct GetInboundShipmentListRequestBuilder {
+  inner: GetInboundShipmentListRequest,
+}
+
+impl GetInboundShipmentListRequest {
+  pub fn new() -> GetInboundShipmentListRequestBuilder {
+    GetInboundShipmentListRequestBuilder {
+      inner: GetInboundShipmentListRequest::default(),
+    }
+  }
+}
+
+impl GetInboundShipmentListRequestBuilder {
+  pub fn finalize(&mut self) -> GetInboundShipmentListRequest {
+    let mut req = ::std::mem::take(&mut self.inner);
+    if req.page_size.is_none() {
+      req.page_size = Some(100);
+    }
+    req
+  }
+
+  pub fn page_index(&mut self, v: i32) -> &mut Self {
+    self.inner.page_index = Some(v);
+    self
+  }
+
+  pub fn page_size(&mut self, v: i32) -> &mut Self {
+    self.inner.page_size = Some(v);
+    self
+  }
+
+  pub fn shipment_id_list(&mut self, v: Vec<String>) -> &mut Self {
+    self.inner.request_criteria.shipment_id_list = Some(v);
+    self
+  }
+
+  pub fn shipment_status(&mut self, v: InboundShipmentStatus) -> &mut Self {
+    self.inner.request_criteria.shipment_status = Some(v);
+    self
+  }
+
+  pub fn create_date_from(&mut self, v: DateTime<Utc>) -> &mut Self {
+    self.inner.request_criteria.create_date_from = Some(NeweggDateTime::from_utc(v));
+    self
+  }
+
+  pub fn create_date_to(&mut self, v: DateTime<Utc>) -> &mut Self {
+    self.inner.request_criteria.create_date_to = Some(NeweggDateTime::from_utc(v));
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InboundShipmentListResponseBody {
+  #[serde(rename = "PageInfo")]
+  pub page_info: PageInfo,
+  #[serde(rename = "ShipmentList")]
+  #[serde(default)]
+  pub shipment_list: Vec<InboundShipmentInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundShipmentInfo {
+  #[serde(rename = "ShipmentID")]
+  pub shipment_id: String,
+  #[serde(rename = "ShipmentStatus")]
+  pub shipment_status: InboundShipmentStatus,
+  #[serde(rename = "ShipToWarehouse")]
+  pub ship_to_warehouse: String,
+  #[serde(rename = "ShippingMethod")]
+  pub shipping_method: Option<InboundShippingMethod>,
+  #[serde(rename = "CreateDate")]
+  pub create_date: Option<NeweggDateTime>,
+  #[serde(rename = "SellerShipmentNumber")]
+  pub seller_shipment_number: Option<String>,
+  #[serde(rename = "ItemList")]
+  #[serde(default)]
+  pub item_list: Vec<InboundShipmentInfoItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InboundShipmentInfoItem {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "NeweggItemNumber")]
+  pub newegg_item_number: Option<String>,
+  #[serde(rename = "ItemQuantity")]
+  pub item_quantity: i32,
+  #[serde(rename = "ReceivedQuantity")]
+  pub received_quantity: Option<i32>,
+}
+
+pub type GetInboundShipmentListResponse = NeweggApiResponse<InboundShipmentListResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/sbn_management/get_package_label/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageLabelResponseBody {
+  #[serde(rename = "ShipmentID")]
+  pub shipment_id: String,
+  #[serde(rename = "LabelList")]
+  #[serde(default)]
+  pub label_list: Vec<LabelDocument>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelDocument {
+  #[serde(rename = "FileName")]
+  pub file_name: Option<String>,
+  #[serde(rename = "FileType")]
+  pub file_type: String,
+  /// Base64 encoded document
+  #[serde(rename = "FileContent")]
+  pub file_content: String,
+}
+
+impl LabelDocument {
+  pub fn decode(&self) -> NeweggResult<Vec<u8>> {
+    Ok(base64::engine::general_purpose::STANDARD.decode(self.file_content.trim())?)
+  }
+}
+
+pub type GetPackageLabelResponse = NeweggApiResponse<PackageLabelResponseBody>;
+
+#[test]
+fn test_sbn_types() {
+  use serde_json::json;
+
+  let plan = InboundShipmentPlan::new("07", InboundShippingMethod::SmallParcel)
+    .seller_shipment_number("S001")
+    .add_item("sku-1", 10)
+    .finalize();
+  assert_eq!(
+    serde_json::to_value(&plan).unwrap(),
+    json!({
+      "Shipment": {
+        "Header": { "ShipToWarehouse": "07", "ShippingMethod": 1, "SellerShipmentNumber": "S001" },
+        "ItemList": { "Item": [{ "SellerPartNumber": "sku-1", "ItemQuantity": 10 }] }
+      }
+    })
+  );
+  let req = GetInboundShipmentListRequest::new()
+    .shipment_status(InboundShipmentStatus::Shipped)
+    .finalize();
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    json!({ "PageSize": 100, "RequestCriteria": { "ShipmentStatus": 1 } })
+  );
+
+  let res: SubmitInboundShipmentResponse = serde_json::from_value(json!({
+    "IsSuccess": true,
+    "OperationType": "SubmitInboundShipmentResponse",
+    "SellerID": "A001",
+    "ResponseBody": {
+      "ResponseList": [{
+        "RequestID": "R001",
+        "RequestDate": "02/12/2018 12:16:43",
+        "RequestStatus": "FINISHED",
+        "ErrorList": { "ErrorDescription": "Invalid warehouse." }
+      }]
+    }
+  }))
+  .unwrap();
+  let info = &res.response_body.response_list[0];
+  assert_eq!(info.request_status, Some(RequestStatus::Finished));
+  assert_eq!(
+    info.error_list.as_ref().unwrap().error_description.as_slice(),
+    ["Invalid warehouse.".to_string()]
+  );
+
+  let res: GetInboundShipmentListResponse = serde_json::from_value(json!({
+    "IsSuccess": true,
+    "OperationType": "GetInboundShipmentListResponse",
+    "SellerID": "A001",
+    "ResponseBody": {
+      "PageInfo": { "TotalCount": 1, "TotalPageCount": 1, "PageIndex": 1, "PageSize": 100 },
+      "ShipmentList": [{
+        "ShipmentID": "SH001",
+        "ShipmentStatus": 3,
+        "ShipToWarehouse": "07",
+        "ItemList": [{ "SellerPartNumber": "sku-1", "ItemQuantity": 10, "ReceivedQuantity": 9 }]
+      }]
+    }
+  }))
+  .unwrap();
+  let shipment = &res.response_body.shipment_list[0];
+  assert_eq!(shipment.shipment_status, InboundShipmentStatus::Received);
+  assert_eq!(shipment.item_list[0].received_quantity, Some(9));
+
+  let res: GetPackageLabelResponse = serde_json::from_value(json!({
+    "IsSuccess": true,
+    "OperationType": "GetPackageLabelResponse",
+    "SellerID": "A001",
+    "ResponseBody": {
+      "ShipmentID": "SH001",
+      "LabelList": [{ "FileType": "PDF", "FileContent": "JVBERg==" }]
+    }
+  }))
+  .unwrap();
+  assert_eq!(res.response_body.label_list[0].decode().unwrap(), b"%PDF");
+}
@@ -0,0 +1,125 @@
+use crate::client::*;
+use crate::result::NeweggResult;
+use crate::types::NeweggApiRequest;
+use async_trait::async_trait;
+use serde_json::json;
+
+mod types;
+
+pub use self::types::*;
+
+#[async_trait]
+pub trait SellerApi {
+  async fn get_seller_status(&self) -> NeweggResult<GetSellerStatusResponse>;
+  async fn get_industry_list(&self) -> NeweggResult<GetIndustryListResponse>;
+  async fn get_subcategory_list(
+    &self,
+    request: &GetSubcategoryRequest,
+  ) -> NeweggResult<GetSubcategoryListResponse>;
+  async fn get_subcategory_property_list(
+    &self,
+    subcategory_id: i64,
+  ) -> NeweggResult<GetSubcategoryPropertyListResponse>;
+  async fn get_property_value_list(
+    &self,
+    subcategory_id: i64,
+    property_name: Option<&str>,
+  ) -> NeweggResult<GetPropertyValueListResponse>;
+  async fn lookup_manufacturer(
+    &self,
+    request: &ManufacturerLookupRequest,
+  ) -> NeweggResult<ManufacturerLookupResponse>;
+}
+
+#[async_trait]
+impl SellerApi for NeweggClient {
+  async fn get_seller_status(&self) -> NeweggResult<GetSellerStatusResponse> {
+    self
+      .send(self.request(Method::GET, "/sellermgmt/seller/accountstatus"))
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_industry_list(&self) -> NeweggResult<GetIndustryListResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sellermgmt/seller/industry")
+          .json(&NeweggApiRequest::new("GetIndustryRequest", json!({}))),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_subcategory_list(
+    &self,
+    request: &GetSubcategoryRequest,
+  ) -> NeweggResult<GetSubcategoryListResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sellermgmt/seller/subcategory")
+          .json(&NeweggApiRequest::new("GetSellerSubcategoryRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_subcategory_property_list(
+    &self,
+    subcategory_id: i64,
+  ) -> NeweggResult<GetSubcategoryPropertyListResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sellermgmt/seller/subcategoryproperty")
+          .json(&NeweggApiRequest::new(
+            "GetSellerSubcategoryPropertyRequest",
+            GetSubcategoryPropertyRequest { subcategory_id },
+          )),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_property_value_list(
+    &self,
+    subcategory_id: i64,
+    property_name: Option<&str>,
+  ) -> NeweggResult<GetPropertyValueListResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/sellermgmt/seller/propertyvalue")
+          .json(&NeweggApiRequest::new(
+            "GetSellerPropertyValueRequest",
+            GetPropertyValueRequest {
+              subcategory_id,
+              property_name: property_name.map(ToOwned::to_owned),
+            },
+          )),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn lookup_manufacturer(
+    &self,
+    request: &ManufacturerLookupRequest,
+  ) -> NeweggResult<ManufacturerLookupResponse> {
+    self
+      .send(
+        self
+          .request(Method::POST, "/sellermgmt/seller/manufacturer")
+          .json(&NeweggApiRequest::new("GetManufacturerRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+}
@@ -0,0 +1,357 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::types::{MaybeList, NeweggApiResponse};
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/get_seller_account_status/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SellerStatusResponseBody {
+  #[serde(rename = "Status")]
+  pub status: String,
+  #[serde(rename = "FufillmentCenterList")]
+  pub fulfillment_center_list: Option<FulfillmentCenterList>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FulfillmentCenterList {
+  #[serde(rename = "WarehouseLocation")]
+  pub warehouse_location: MaybeList<String>,
+}
+
+pub type GetSellerStatusResponse = NeweggApiResponse<SellerStatusResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/get_industry_list/
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndustryListResponseBody {
+  #[serde(rename = "IndustryList")]
+  #[serde(default)]
+  pub industry_list: Vec<Industry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Industry {
+  #[serde(rename = "IndustryCode")]
+  pub industry_code: String,
+  #[serde(rename = "IndustryName")]
+  pub industry_name: String,
+}
+
+pub type GetIndustryListResponse = NeweggApiResponse<IndustryListResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/get_subcategory_status/
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct GetSubcategoryRequest {
+  #[serde(rename = "GetItemSubcategory")]
+  pub get_item_subcategory: GetItemSubcategory,
+}
+
+impl GetSubcategoryRequest {
+  pub fn new() -> GetSubcategoryRequestBuilder {
+    GetSubcategoryRequestBuilder {
+      inner: GetSubcategoryRequest::default(),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct GetItemSubcategory {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "IndustryCodeList")]
+  pub industry_code_list: Option<IndustryCodeList>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "SubcategoryIDList")]
+  pub subcategory_id_list: Option<SubcategoryIdList>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Enabled")]
+  pub enabled: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct IndustryCodeList {
+  #[serde(rename = "IndustryCode")]
+  pub industry_code: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SubcategoryIdList {
+  #[serde(rename = "SubcategoryID")]
+  pub subcategory_id: Vec<i64>,
+}
+
+pub struct GetSubcategoryRequestBuilder {
+  inner: GetSubcategoryRequest,
+}
+
+impl GetSubcategoryRequestBuilder {
+  pub fn finalize(&mut self) -> GetSubcategoryRequest {
+    ::std::mem::take(&mut self.inner)
+  }
+
+  pub fn industry_code_list(&mut self, v: Vec<String>) -> &mut Self {
+    self.inner.get_item_subcategory.industry_code_list = Some(IndustryCodeList { industry_code: v });
+    self
+  }
+
+  pub fn subcategory_id_list(&mut self, v: Vec<i64>) -> &mut Self {
+    self.inner.get_item_subcategory.subcategory_id_list =
+      Some(SubcategoryIdList { subcategory_id: v });
+    self
+  }
+
+  pub fn enabled(&mut self, v: bool) -> &mut Self {
+    self.inner.get_item_subcategory.enabled = Some(if v { 1 } else { 0 });
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubcategoryListResponseBody {
+  #[serde(rename = "SubCategoryList")]
+  #[serde(default)]
+  pub subcategory_list: Vec<Subcategory>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Subcategory {
+  #[serde(rename = "IndustryCode")]
+  pub industry_code: String,
+  #[serde(rename = "IndustryName")]
+  pub industry_name: String,
+  #[serde(rename = "SubcategoryID")]
+  pub subcategory_id: i64,
+  #[serde(rename = "SubcategoryName")]
+  pub subcategory_name: String,
+  #[serde(rename = "Enabled")]
+  pub enabled: i32,
+}
+
+pub type GetSubcategoryListResponse = NeweggApiResponse<SubcategoryListResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/get_subcategory_properties/
+#[derive(Debug, Serialize, Clone)]
+pub struct GetSubcategoryPropertyRequest {
+  #[serde(rename = "SubcategoryID")]
+  pub subcategory_id: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubcategoryPropertyListResponseBody {
+  #[serde(rename = "SubcategoryPropertyList")]
+  #[serde(default)]
+  pub subcategory_property_list: Vec<SubcategoryProperty>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubcategoryProperty {
+  #[serde(rename = "SubcategoryID")]
+  pub subcategory_id: i64,
+  #[serde(rename = "SubcategoryName")]
+  pub subcategory_name: String,
+  #[serde(rename = "PropertyName")]
+  pub property_name: String,
+  #[serde(rename = "IsAdvancedSearch")]
+  pub is_advanced_search: i32,
+  #[serde(rename = "IsGroupBy")]
+  pub is_group_by: i32,
+  #[serde(rename = "IsRequired")]
+  pub is_required: i32,
+}
+
+pub type GetSubcategoryPropertyListResponse = NeweggApiResponse<SubcategoryPropertyListResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/get_property_value/
+#[derive(Debug, Serialize, Clone)]
+pub struct GetPropertyValueRequest {
+  #[serde(rename = "SubcategoryID")]
+  pub subcategory_id: i64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PropertyName")]
+  pub property_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PropertyValueListResponseBody {
+  #[serde(rename = "PropertyInfoList")]
+  #[serde(default)]
+  pub property_info_list: Vec<PropertyInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyInfo {
+  #[serde(rename = "PropertyName")]
+  pub property_name: String,
+  #[serde(rename = "PropertyValueList")]
+  #[serde(default)]
+  pub property_value_list: Vec<PropertyValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyValue {
+  #[serde(rename = "ValueName")]
+  pub value_name: String,
+  #[serde(rename = "IsDefault")]
+  pub is_default: i32,
+}
+
+pub type GetPropertyValueListResponse = NeweggApiResponse<PropertyValueListResponseBody>;
+
+/// https://developer.newegg.com/newegg_marketplace_api/seller_management/manufacturer_lookup/
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ManufacturerLookupRequest {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageIndex")]
+  pub page_index: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "PageSize")]
+  pub page_size: Option<i32>,
+  #[serde(rename = "RequestCriteria")]
+  pub request_criteria: ManufacturerRequestCriteria,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ManufacturerRequestCriteria {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "CompanyName")]
+  pub company_name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "Status")]
+  pub status: Option<i32>,
+}
+
+impl ManufacturerLookupRequest {
+  pub fn new() -> ManufacturerLookupRequestBuilder {
+    ManufacturerLookupRequestBuilder {
+      inner: ManufacturerLookupRequest::default(),
+    }
+  }
+}
+
+enum_number! {
+  ManufacturerStatus {
+    Approved = 0,
+    Pending = 1,
+    Declined = 2,
+  }
+}
+
+pub struct ManufacturerLookupRequestBuilder {
+  inner: ManufacturerLookupRequest,
+}
+
+impl ManufacturerLookupRequestBuilder {
+  pub fn finalize(&mut self) -> ManufacturerLookupRequest {
+    let mut req = ::std::mem::take(&mut self.inner);
+    if req.page_size.is_none() {
+      req.page_size = Some(100);
+    }
+    req
+  }
+
+  pub fn page_index(&mut self, v: i32) -> &mut Self {
+    self.inner.page_index = Some(v);
+    self
+  }
+
+  pub fn page_size(&mut self, v: i32) -> &mut Self {
+    self.inner.page_size = Some(v);
+    self
+  }
+
+  pub fn company_name(&mut self, v: &str) -> &mut Self {
+    self.inner.request_criteria.company_name = Some(v.to_owned());
+    self
+  }
+
+  pub fn status(&mut self, v: ManufacturerStatus) -> &mut Self {
+    self.inner.request_criteria.status = Some(v as i32);
+    self
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManufacturerListResponseBody {
+  #[serde(rename = "PageInfo")]
+  pub page_info: Option<ManufacturerPageInfo>,
+  #[serde(rename = "ManufacturerList")]
+  #[serde(default)]
+  pub manufacturer_list: Vec<Manufacturer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManufacturerPageInfo {
+  #[serde(rename = "TotalCount")]
+  pub total_count: i64,
+  #[serde(rename = "TotalPageCount")]
+  pub total_page_count: i64,
+  #[serde(rename = "PageIndex")]
+  pub page_index: i64,
+  #[serde(rename = "PageSize")]
+  pub page_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Manufacturer {
+  #[serde(rename = "Name")]
+  pub name: String,
+  #[serde(rename = "Status")]
+  pub status: Option<ManufacturerStatus>,
+  #[serde(rename = "IsRestricted")]
+  pub is_restricted: Option<i32>,
+  #[serde(rename = "URL")]
+  pub url: Option<String>,
+  #[serde(rename = "SupportEmail")]
+  pub support_email: Option<String>,
+  #[serde(rename = "SupportPhone")]
+  pub support_phone: Option<String>,
+  #[serde(rename = "SupportURL")]
+  pub support_url: Option<String>,
+}
+
+pub type ManufacturerLookupResponse = NeweggApiResponse<ManufacturerListResponseBody>;
+
+#[test]
+fn test_seller_types() {
+  use serde_json::json;
+
+  let req = ManufacturerLookupRequest::new().company_name("Ventmere").finalize();
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    json!({ "PageSize": 100, "RequestCriteria": { "CompanyName": "Ventmere" } })
+  );
+  let req = GetSubcategoryRequest::new()
+    .industry_code_list(vec!["CH".to_string()])
+    .enabled(true)
+    .finalize();
+  assert_eq!(
+    serde_json::to_value(&req).unwrap(),
+    json!({ "GetItemSubcategory": { "IndustryCodeList": { "IndustryCode": ["CH"] }, "Enabled": 1 } })
+  );
+
+  let res: GetSellerStatusResponse = serde_json::from_value(json!({
+    "IsSuccess": true,
+    "OperationType": "GetSellerAccountStatusResponse",
+    "SellerID": "A001",
+    "ResponseBody": {
+      "Status": "Active",
+      "FufillmentCenterList": { "WarehouseLocation": "USA" }
+    }
+  }))
+  .unwrap();
+  match res.response_body.fulfillment_center_list.unwrap().warehouse_location {
+    MaybeList::Single(location) => assert_eq!(location, "USA"),
+    other => panic!("unexpected warehouse list: {:?}", other),
+  }
+
+  let res: ManufacturerLookupResponse = serde_json::from_value(json!({
+    "IsSuccess": true,
+    "OperationType": "GetManufacturerResponse",
+    "SellerID": "A001",
+    "ResponseBody": {
+      "PageInfo": { "TotalCount": 1, "TotalPageCount": 1, "PageIndex": 1, "PageSize": 100 },
+      "ManufacturerList": [{ "Name": "Ventmere", "Status": 0, "IsRestricted": 0 }]
+    }
+  }))
+  .unwrap();
+  let manufacturer = &res.response_body.manufacturer_list[0];
+  assert_eq!(manufacturer.status, Some(ManufacturerStatus::Approved));
+  assert!(manufacturer.url.is_none());
+}
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use crate::client::*;
 use crate::result::NeweggResult;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ServiceStatusDomain {
   Content,
   Order,
@@ -29,6 +30,7 @@ impl ServiceStatusDomain {
     }
   }
 
+  #[allow(clippy::should_implement_trait)]
   pub fn from_str(v: &str) -> Option<Self> {
     match v {
       "contentmgmt" => Some(ServiceStatusDomain::Content),
@@ -42,6 +44,11 @@ impl ServiceStatusDomain {
       _ => None,
     }
   }
+
+  /// Finds the domain of a request path like `/marketplace/can/ordermgmt/order/orderinfo`.
+  pub fn from_path(path: &str) -> Option<Self> {
+    path.split('/').find_map(Self::from_str)
+  }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -85,8 +92,7 @@ impl ServiceStatusApi for NeweggClient {
     domain: ServiceStatusDomain,
   ) -> NeweggResult<GetServiceStatusResponse> {
     self
-      .request(Method::GET, &format!("/{}/servicestatus", domain.as_str()))
-      .send()
+      .send(self.request(Method::GET, &format!("/{}/servicestatus", domain.as_str())))
       .await?
       .get_response()
       .await
@@ -0,0 +1,108 @@
+use crate::client::*;
+use crate::result::{NeweggError, NeweggResult};
+use crate::types::NeweggApiRequest;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde_json::json;
+
+mod types;
+
+pub use self::types::*;
+
+#[async_trait]
+pub trait ShippingLabelApi {
+  async fn get_shipping_label_quote(
+    &self,
+    request: &ShippingLabelRequest,
+  ) -> NeweggResult<ShippingQuoteResponse>;
+  async fn submit_shipping_label_request(
+    &self,
+    request: &ShippingLabelRequest,
+  ) -> NeweggResult<SubmitShippingLabelResponse>;
+  async fn get_shipping_label_status(
+    &self,
+    request_id: &str,
+  ) -> NeweggResult<ShippingLabelStatusResponse>;
+  /// Downloads a PDF label from a `LabelPackageResult::label_url`.
+  async fn download_shipping_label(&self, label_url: &str) -> NeweggResult<Vec<u8>>;
+  async fn void_shipping_label(
+    &self,
+    request: &VoidShippingLabelRequest,
+  ) -> NeweggResult<VoidShippingLabelResponse>;
+}
+
+#[async_trait]
+impl ShippingLabelApi for NeweggClient {
+  async fn get_shipping_label_quote(
+    &self,
+    request: &ShippingLabelRequest,
+  ) -> NeweggResult<ShippingQuoteResponse> {
+    self
+      .send(
+        self
+          .request(Method::POST, "/shippinglabelmgmt/shippinglabel/quote")
+          .json(&NeweggApiRequest::new("GetShippingQuoteRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn submit_shipping_label_request(
+    &self,
+    request: &ShippingLabelRequest,
+  ) -> NeweggResult<SubmitShippingLabelResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::POST, "/shippinglabelmgmt/shippinglabel/submitrequest")
+          .json(&NeweggApiRequest::new("SubmitShippingRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn get_shipping_label_status(
+    &self,
+    request_id: &str,
+  ) -> NeweggResult<ShippingLabelStatusResponse> {
+    self
+      .send(
+        self
+          .request(Method::PUT, "/shippinglabelmgmt/shippinglabel/requeststatus")
+          .json(&NeweggApiRequest::new(
+            "GetShippingRequestStatus",
+            json!({ "RequestID": request_id }),
+          )),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+
+  async fn download_shipping_label(&self, label_url: &str) -> NeweggResult<Vec<u8>> {
+    // a plain download, not a signed API call
+    let res = self.http().get(label_url).send().await?;
+    let status = res.status();
+    if status != StatusCode::OK {
+      return Err(NeweggError::request(label_url, status, &res.text().await?));
+    }
+    Ok(res.bytes().await?.to_vec())
+  }
+
+  async fn void_shipping_label(
+    &self,
+    request: &VoidShippingLabelRequest,
+  ) -> NeweggResult<VoidShippingLabelResponse> {
+    self
+      .send_non_idempotent(
+        self
+          .request(Method::PUT, "/shippinglabelmgmt/shippinglabel/void")
+          .json(&NeweggApiRequest::new("VoidShippingRequest", request)),
+      )
+      .await?
+      .get_response()
+      .await
+  }
+}
@@ -0,0 +1,434 @@
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+use crate::feed::RequestStatus;
+use crate::helpers::NeweggDateTime;
+use crate::order::{OrderInfo, ShipCarrier};
+use crate::result::{NeweggError, NeweggResult};
+use crate::types::{MaybeList, NeweggApiResponse};
+
+/// https://developer.newegg.com/newegg_marketplace_api/shipping_label_management/submit_shipping_request/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingLabelRequest {
+  #[serde(rename = "Shipment")]
+  pub shipment: LabelShipment,
+}
+
+impl ShippingLabelRequest {
+  pub fn new(order_number: i64, ship_to: ShipToAddress) -> ShippingLabelRequestBuilder {
+    ShippingLabelRequestBuilder {
+      inner: LabelShipment {
+        order_number,
+        ship_carrier: None,
+        ship_service: None,
+        ship_to_address: ship_to,
+        package_list: LabelPackageList { package: vec![] },
+      },
+    }
+  }
+
+  /// Creates a builder with the ship-to address of `order`.
+  /// Use `ShippingLabelRequestBuilder::add_unshipped_items` to label every item not shipped yet.
+  pub fn from_order(order: &OrderInfo) -> NeweggResult<ShippingLabelRequestBuilder> {
+    Ok(Self::new(order.order_number, ShipToAddress::try_from(order)?))
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelShipment {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: i64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShipCarrier")]
+  pub ship_carrier: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(rename = "ShipService")]
+  pub ship_service: Option<String>,
+  #[serde(rename = "ShipToAddress")]
+  pub ship_to_address: ShipToAddress,
+  #[serde(rename = "PackageList")]
+  pub package_list: LabelPackageList,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ShipToAddress {
+  #[serde(rename = "FirstName")]
+  pub first_name: String,
+  #[serde(rename = "LastName")]
+  pub last_name: String,
+  #[serde(rename = "Company")]
+  pub company: String,
+  #[serde(rename = "Address1")]
+  pub address1: String,
+  #[serde(rename = "Address2")]
+  pub address2: String,
+  #[serde(rename = "City")]
+  pub city: String,
+  #[serde(rename = "StateCode")]
+  pub state_code: String,
+  #[serde(rename = "ZipCode")]
+  pub zip_code: String,
+  #[serde(rename = "CountryCode")]
+  pub country_code: String,
+  #[serde(rename = "PhoneNumber")]
+  pub phone_number: String,
+}
+
+/// Orders carry the country name, e.g. "UNITED STATES", fails if it has no
+/// known ISO 3166-1 alpha-2 code.
+impl<'a> TryFrom<&'a OrderInfo> for ShipToAddress {
+  type Error = NeweggError;
+
+  fn try_from(order: &'a OrderInfo) -> NeweggResult<Self> {
+    let country_code = iso_country_code(&order.ship_to_country_code)
+      .ok_or_else(|| NeweggError::UnknownCountry(order.ship_to_country_code.clone()))?;
+    Ok(ShipToAddress {
+      first_name: order.ship_to_first_name.clone(),
+      last_name: order.ship_to_last_name.clone(),
+      company: order.ship_to_company.clone(),
+      address1: order.ship_to_address1.clone(),
+      address2: order.ship_to_address2.clone(),
+      city: order.ship_to_city_name.clone(),
+      state_code: order.ship_to_state_code.clone(),
+      zip_code: order.ship_to_zip_code.clone(),
+      country_code: country_code.to_string(),
+      phone_number: order.customer_phone_number.clone(),
+    })
+  }
+}
+
+const COUNTRY_CODES: &[(&str, &str)] = &[
+  ("UNITED STATES", "US"),
+  ("USA", "US"),
+  ("CANADA", "CA"),
+  ("PUERTO RICO", "PR"),
+  ("GUAM", "GU"),
+  ("VIRGIN ISLANDS (U.S.)", "VI"),
+  ("U.S. VIRGIN ISLANDS", "VI"),
+  ("AMERICAN SAMOA", "AS"),
+  ("NORTHERN MARIANA ISLANDS", "MP"),
+  ("MEXICO", "MX"),
+  ("UNITED KINGDOM", "GB"),
+  ("AUSTRALIA", "AU"),
+  ("NEW ZEALAND", "NZ"),
+  ("SINGAPORE", "SG"),
+  ("TAIWAN", "TW"),
+  ("JAPAN", "JP"),
+  ("KOREA, REPUBLIC OF", "KR"),
+  ("SOUTH KOREA", "KR"),
+  ("HONG KONG", "HK"),
+  ("CHINA", "CN"),
+  ("GERMANY", "DE"),
+  ("FRANCE", "FR"),
+  ("ISRAEL", "IL"),
+  ("SAUDI ARABIA", "SA"),
+  ("UNITED ARAB EMIRATES", "AE"),
+];
+
+/// ISO 3166-1 alpha-2 code of a country name used in orders, case insensitive.
+/// Codes of the table are returned as is.
+pub fn iso_country_code(name: &str) -> Option<&'static str> {
+  let name = name.trim();
+  COUNTRY_CODES
+    .iter()
+    .find(|(n, code)| n.eq_ignore_ascii_case(name) || code.eq_ignore_ascii_case(name))
+    .map(|(_, code)| *code)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelPackageList {
+  #[serde(rename = "Package")]
+  pub package: Vec<LabelPackage>,
+}
+
+/// Weight in pounds, dimensions in inches.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LabelPackage {
+  #[serde(rename = "PackageWeight", with = "crate::helpers::decimal")]
+  pub package_weight: BigDecimal,
+  #[serde(rename = "PackageLength", with = "crate::helpers::decimal")]
+  pub package_length: BigDecimal,
+  #[serde(rename = "PackageWidth", with = "crate::helpers::decimal")]
+  pub package_width: BigDecimal,
+  #[serde(rename = "PackageHeight", with = "crate::helpers::decimal")]
+  pub package_height: BigDecimal,
+  #[serde(rename = "ItemList")]
+  pub item_list: Vec<LabelItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelItem {
+  #[serde(rename = "SellerPartNumber")]
+  pub seller_part_number: String,
+  #[serde(rename = "Quantity")]
+  pub quantity: i64,
+}
+
+pub struct ShippingLabelRequestBuilder {
+  inner: LabelShipment,
+}
+
+impl ShippingLabelRequestBuilder {
+  pub fn ship_carrier(&mut self, v: ShipCarrier) -> &mut Self {
+    self.inner.ship_carrier = Some(v.to_string());
+    self
+  }
+
+  pub fn ship_service(&mut self, v: &str) -> &mut Self {
+    self.inner.ship_service = Some(v.to_owned());
+    self
+  }
+
+  pub fn add_package(&mut self, package: LabelPackage) -> &mut Self {
+    self.inner.package_list.package.push(package);
+    self
+  }
+
+  /// Adds one package containing every item of `order` that is not fully shipped.
+  pub fn add_unshipped_items(
+    &mut self,
+    order: &OrderInfo,
+    weight: BigDecimal,
+    (length, width, height): (BigDecimal, BigDecimal, BigDecimal),
+  ) -> &mut Self {
+    let item_list = order
+      .item_info_list
+      .iter()
+      .filter(|item| item.ordered_qty > item.shipped_qty)
+      .map(|item| LabelItem {
+        seller_part_number: item.seller_part_number.clone(),
+        quantity: item.ordered_qty - item.shipped_qty,
+      })
+      .collect();
+    self.add_package(LabelPackage {
+      package_weight: weight,
+      package_length: length,
+      package_width: width,
+      package_height: height,
+      item_list,
+    })
+  }
+
+  pub fn finalize(&mut self) -> ShippingLabelRequest {
+    let replace = LabelShipment {
+      package_list: LabelPackageList { package: vec![] },
+      ..self.inner.clone()
+    };
+    ShippingLabelRequest {
+      shipment: ::std::mem::replace(&mut self.inner, replace),
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShippingQuoteResponseBody {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: i64,
+  #[serde(rename = "QuoteList")]
+  #[serde(default)]
+  pub quote_list: Vec<ShippingQuote>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingQuote {
+  #[serde(rename = "ShipCarrier")]
+  pub ship_carrier: String,
+  #[serde(rename = "ShipService")]
+  pub ship_service: String,
+  #[serde(rename = "Charge", with = "crate::helpers::decimal")]
+  pub charge: BigDecimal,
+  #[serde(rename = "Currency")]
+  pub currency: Option<String>,
+  #[serde(rename = "EstimatedDeliveryDays")]
+  pub estimated_delivery_days: Option<i32>,
+}
+
+pub type ShippingQuoteResponse = NeweggApiResponse<ShippingQuoteResponseBody>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitShippingLabelResponseBody {
+  #[serde(rename = "RequestID")]
+  pub request_id: String,
+  #[serde(rename = "RequestDate")]
+  pub request_date: Option<NeweggDateTime>,
+  #[serde(rename = "RequestStatus")]
+  pub request_status: Option<RequestStatus>,
+}
+
+pub type SubmitShippingLabelResponse = NeweggApiResponse<SubmitShippingLabelResponseBody>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShippingLabelStatusResponseBody {
+  #[serde(rename = "RequestID")]
+  pub request_id: String,
+  #[serde(rename = "RequestStatus")]
+  pub request_status: RequestStatus,
+  #[serde(rename = "OrderNumber")]
+  pub order_number: Option<i64>,
+  #[serde(rename = "PackageList")]
+  #[serde(default)]
+  pub package_list: Vec<LabelPackageResult>,
+}
+
+impl ShippingLabelStatusResponseBody {
+  pub fn label_urls(&self) -> impl Iterator<Item = &str> {
+    self
+      .package_list
+      .iter()
+      .filter_map(|p| p.label_url.as_ref().map(AsRef::as_ref))
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelPackageResult {
+  #[serde(rename = "TrackingNumber")]
+  pub tracking_number: Option<String>,
+  #[serde(rename = "ShipCarrier")]
+  pub ship_carrier: Option<String>,
+  #[serde(rename = "ShipService")]
+  pub ship_service: Option<String>,
+  #[serde(rename = "Charge", default, with = "crate::helpers::option_decimal")]
+  pub charge: Option<BigDecimal>,
+  #[serde(rename = "LabelURL")]
+  pub label_url: Option<String>,
+  #[serde(rename = "ErrorDescription")]
+  pub error_description: Option<MaybeList<String>>,
+}
+
+pub type ShippingLabelStatusResponse = NeweggApiResponse<ShippingLabelStatusResponseBody>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoidShippingLabelRequest {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: i64,
+  #[serde(rename = "TrackingNumberList")]
+  pub tracking_number_list: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VoidShippingLabelResponseBody {
+  #[serde(rename = "OrderNumber")]
+  pub order_number: i64,
+  #[serde(rename = "VoidResultList")]
+  #[serde(default)]
+  pub void_result_list: Vec<VoidResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoidResult {
+  #[serde(rename = "TrackingNumber")]
+  pub tracking_number: String,
+  #[serde(rename = "IsSuccess")]
+  pub is_success: bool,
+  #[serde(rename = "ErrorDescription")]
+  pub error_description: Option<String>,
+}
+
+pub type VoidShippingLabelResponse = NeweggApiResponse<VoidShippingLabelResponseBody>;
+
+#[test]
+fn test_shipping_label_request_from_order() {
+  let order: OrderInfo = serde_json::from_str(
+    r#"{
+      "CustomerEmailAddress": "customer@example.com",
+      "CustomerName": "John Doe",
+      "CustomerPhoneNumber": "626-000-0000",
+      "DiscountAmount": 0.0,
+      "InvoiceNumber": 0,
+      "IsAutoVoid": false,
+      "ItemInfoList": [
+        {
+          "Description": "Speaker",
+          "ExtendShippingCharge": 0.0,
+          "ExtendUnitPrice": 99.99,
+          "MfrPartNumber": "R1280T",
+          "NeweggItemNumber": "9SIA0000000001",
+          "OrderedQty": 2,
+          "SellerPartNumber": "edifier-r1280t",
+          "ShippedQty": 1,
+          "Status": 1,
+          "StatusDescription": "Partially Shipped",
+          "UPCCode": "",
+          "UnitPrice": 99.99
+        },
+        {
+          "Description": "Cable",
+          "ExtendShippingCharge": 0.0,
+          "ExtendUnitPrice": 9.99,
+          "MfrPartNumber": "C1",
+          "NeweggItemNumber": "9SIA0000000002",
+          "OrderedQty": 1,
+          "SellerPartNumber": "cable",
+          "ShippedQty": 1,
+          "Status": 2,
+          "StatusDescription": "Shipped",
+          "UPCCode": "",
+          "UnitPrice": 9.99
+        }
+      ],
+      "OrderDate": "02/12/2018 12:16:43",
+      "OrderDownloaded": true,
+      "OrderItemAmount": 109.98,
+      "OrderNumber": 123456789,
+      "OrderQty": 3,
+      "OrderStatus": 1,
+      "OrderStatusDescription": "Partially Shipped",
+      "OrderTotalAmount": 109.98,
+      "PackageInfoList": [],
+      "RefundAmount": 0.0,
+      "SellerID": "A000",
+      "ShipService": "Standard Shipping (5-7 business days)",
+      "ShipToAddress1": "17560 Rowland St",
+      "ShipToAddress2": "",
+      "ShipToCityName": "City of Industry",
+      "ShipToCompany": "",
+      "ShipToCountryCode": "UNITED STATES",
+      "ShipToFirstName": "John",
+      "ShipToLastName": "Doe",
+      "ShipToStateCode": "CA",
+      "ShipToZipCode": "91748",
+      "ShippingAmount": 0.0
+    }"#,
+  )
+  .unwrap();
+
+  let dec = |v: &str| v.parse::<BigDecimal>().unwrap();
+  let req = ShippingLabelRequest::from_order(&order)
+    .unwrap()
+    .ship_carrier(ShipCarrier::Ups)
+    .add_unshipped_items(&order, dec("3.5"), (dec("12"), dec("8"), dec("6.1")))
+    .finalize();
+
+  assert_eq!(req.shipment.order_number, 123456789);
+  assert_eq!(req.shipment.ship_to_address.zip_code, "91748");
+  assert_eq!(req.shipment.ship_to_address.country_code, "US");
+  assert_eq!(req.shipment.ship_carrier.as_deref(), Some("UPS"));
+  let items = &req.shipment.package_list.package[0].item_list;
+  assert_eq!(items.len(), 1);
+  assert_eq!(items[0].seller_part_number, "edifier-r1280t");
+  assert_eq!(items[0].quantity, 1);
+  let package = serde_json::to_value(&req.shipment.package_list.package[0]).unwrap();
+  assert_eq!(package["PackageWeight"], serde_json::json!(3.5));
+  assert_eq!(package["PackageHeight"], serde_json::json!(6.1));
+
+  let order = OrderInfo {
+    ship_to_country_code: "ATLANTIS".to_string(),
+    ..order
+  };
+  match ShippingLabelRequest::from_order(&order) {
+    Err(NeweggError::UnknownCountry(name)) => assert_eq!(name, "ATLANTIS"),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+}
+
+#[test]
+fn test_shipping_quote_charge() {
+  let quote: ShippingQuote = serde_json::from_str(
+    r#"{"ShipCarrier": "UPS", "ShipService": "Ground", "Charge": 12.34}"#,
+  )
+  .unwrap();
+  assert_eq!(quote.charge, "12.34".parse::<BigDecimal>().unwrap());
+  assert_eq!(iso_country_code("canada"), Some("CA"));
+  assert_eq!(iso_country_code("US"), Some("US"));
+}
@@ -0,0 +1,383 @@
+//! Keeps Newegg inventory in line with a desired stock level.
+//!
+//! The current stock comes from the daily inventory report, only the
+//! differences are sent as `INVENTORY_DATA` feeds. Warehouse locations are
+//! compared and sent trimmed and upper case, e.g. `USA`.
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use std::collections::{HashMap, HashSet};
+
+use crate::client::NeweggClient;
+use crate::feed::message::{
+  InventoryUpdateFeedInventory, InventoryUpdateFeedItem, InventoryUpdateFeedMessage,
+};
+use crate::feed::{FeedApi, PollOptions, ProcessingReport};
+use crate::report::{
+  FulfillType, InventoryReportCriteria, InventoryReportRow, ReportApi, RunReportOptions,
+};
+use crate::result::{NeweggError, NeweggResult};
+
+/// Seller part number → warehouse location → quantity.
+pub type DesiredInventory = HashMap<String, HashMap<String, i32>>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct InventorySyncOptions {
+  /// Maximum number of items per feed.
+  pub batch_size: usize,
+  /// Set the stock of listed items missing from the desired inventory to zero.
+  pub zero_missing: bool,
+  /// Items of the inventory report to sync, seller fulfilled by default.
+  pub fulfill_type: FulfillType,
+  pub report: RunReportOptions,
+  /// Wait for every submitted feed and collect its processing report.
+  pub wait_for_feeds: Option<PollOptions>,
+}
+
+impl Default for InventorySyncOptions {
+  fn default() -> Self {
+    InventorySyncOptions {
+      batch_size: 5000,
+      zero_missing: false,
+      fulfill_type: FulfillType::ShippedBySeller,
+      report: RunReportOptions::default(),
+      wait_for_feeds: None,
+    }
+  }
+}
+
+impl InventorySyncOptions {
+  pub fn batch_size(self, v: usize) -> Self {
+    Self {
+      batch_size: v,
+      ..self
+    }
+  }
+
+  pub fn zero_missing(self, v: bool) -> Self {
+    Self {
+      zero_missing: v,
+      ..self
+    }
+  }
+
+  pub fn fulfill_type(self, v: FulfillType) -> Self {
+    Self {
+      fulfill_type: v,
+      ..self
+    }
+  }
+
+  pub fn report(self, v: RunReportOptions) -> Self {
+    Self { report: v, ..self }
+  }
+
+  pub fn wait_for_feeds(self, v: PollOptions) -> Self {
+    Self {
+      wait_for_feeds: Some(v),
+      ..self
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryChange {
+  pub seller_part_number: String,
+  pub warehouse_location: String,
+  pub current: i32,
+  pub desired: i32,
+}
+
+/// Difference between the desired inventory and the inventory report.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryPlan {
+  pub changes: Vec<InventoryChange>,
+  /// Number of items already at the desired quantity
+  pub unchanged: usize,
+  /// Desired seller part numbers without any row in the inventory report
+  pub not_listed: Vec<String>,
+  /// Desired seller part numbers and warehouse locations without a row,
+  /// for seller part numbers that have rows at other locations
+  pub unmatched: Vec<(String, String)>,
+}
+
+impl InventoryPlan {
+  /// Every report row is matched at most once. Rows without a warehouse
+  /// location (Canada) match a desired location that has no row of its own.
+  pub fn diff(desired: &DesiredInventory, current: &[InventoryReportRow], zero_missing: bool) -> Self {
+    let mut listed: HashMap<(&str, String), Vec<usize>> = HashMap::new();
+    for (i, row) in current.iter().enumerate() {
+      listed
+        .entry((
+          row.seller_part_number.trim(),
+          normalize_location(&row.warehouse_location),
+        ))
+        .or_default()
+        .push(i);
+    }
+
+    // exact locations first, so a blank location row is only used as a fallback
+    let mut wanted: Vec<(&str, String, i32)> = desired
+      .iter()
+      .flat_map(|(seller_part_number, locations)| {
+        locations.iter().map(move |(location, &quantity)| {
+          (seller_part_number.trim(), normalize_location(location), quantity)
+        })
+      })
+      .collect();
+    wanted.sort();
+
+    let mut plan = InventoryPlan::default();
+    let mut matched: HashSet<usize> = HashSet::new();
+    let mut unmatched: Vec<(&str, String, i32)> = vec![];
+    for (sku, location, quantity) in wanted {
+      match take_row(&listed, &mut matched, sku, &location) {
+        Some(i) => plan.add(&current[i], location, quantity),
+        None => unmatched.push((sku, location, quantity)),
+      }
+    }
+    for (sku, location, quantity) in unmatched {
+      match take_row(&listed, &mut matched, sku, "") {
+        Some(i) => plan.add(&current[i], location, quantity),
+        None if listed.keys().any(|(listed_sku, _)| *listed_sku == sku) => {
+          plan.unmatched.push((sku.to_string(), location));
+        }
+        None => {
+          if !plan.not_listed.iter().any(|v| v == sku) {
+            plan.not_listed.push(sku.to_string());
+          }
+        }
+      }
+    }
+
+    if zero_missing {
+      for (i, row) in current.iter().enumerate() {
+        if row.inventory != 0 && !matched.contains(&i) {
+          plan.changes.push(InventoryChange {
+            seller_part_number: row.seller_part_number.trim().to_string(),
+            warehouse_location: normalize_location(&row.warehouse_location),
+            current: row.inventory,
+            desired: 0,
+          });
+        }
+      }
+    }
+
+    plan.changes.sort_by(|a, b| {
+      (&a.seller_part_number, &a.warehouse_location).cmp(&(&b.seller_part_number, &b.warehouse_location))
+    });
+    plan.not_listed.sort();
+    plan.unmatched.sort();
+    plan
+  }
+
+  fn add(&mut self, row: &InventoryReportRow, warehouse_location: String, desired: i32) {
+    if row.inventory == desired {
+      self.unchanged += 1;
+    } else {
+      self.changes.push(InventoryChange {
+        seller_part_number: row.seller_part_number.trim().to_string(),
+        warehouse_location,
+        current: row.inventory,
+        desired,
+      });
+    }
+  }
+
+  pub fn batches(&self, batch_size: usize) -> Vec<InventoryUpdateFeedMessage> {
+    self
+      .changes
+      .chunks(batch_size.max(1))
+      .map(|chunk| InventoryUpdateFeedMessage {
+        inventory: InventoryUpdateFeedInventory {
+          item: chunk
+            .iter()
+            .map(|change| InventoryUpdateFeedItem {
+              seller_part_number: change.seller_part_number.clone(),
+              warehouse_location: change.warehouse_location.clone(),
+              inventory: change.desired.to_string(),
+              ..Default::default()
+            })
+            .collect(),
+        },
+      })
+      .collect()
+  }
+}
+
+fn normalize_location(location: &str) -> String {
+  location.trim().to_uppercase()
+}
+
+/// First row of `listed` for the seller part number and location that is not matched yet.
+fn take_row(
+  listed: &HashMap<(&str, String), Vec<usize>>,
+  matched: &mut HashSet<usize>,
+  sku: &str,
+  location: &str,
+) -> Option<usize> {
+  let i = *listed
+    .get(&(sku, location.to_string()))?
+    .iter()
+    .find(|i| !matched.contains(i))?;
+  matched.insert(i);
+  Some(i)
+}
+
+/// Result of `InventorySync::sync_inventory`. Batches are referred to by
+/// their index in `InventoryPlan::batches`.
+#[derive(Debug, Default)]
+pub struct InventorySyncSummary {
+  pub plan: InventoryPlan,
+  /// Batch index and request id of every submitted feed
+  pub feed_request_ids: Vec<(usize, String)>,
+  /// Batch index and processing report of every submitted feed, only
+  /// filled with `InventorySyncOptions::wait_for_feeds`
+  pub feed_reports: Vec<(usize, ProcessingReport)>,
+  /// Batches that failed to submit, or whose feed could not be waited for
+  pub failed: Vec<(usize, NeweggError)>,
+}
+
+#[async_trait]
+pub trait InventorySync {
+  /// Downloads the daily inventory report and diffs it against `desired`.
+  async fn plan_inventory_sync(
+    &self,
+    desired: &DesiredInventory,
+    options: InventorySyncOptions,
+  ) -> NeweggResult<InventoryPlan>;
+  /// Plans a sync and submits the changes as inventory update feeds,
+  /// optionally waiting for their processing reports. Only planning errors
+  /// are returned, a failed batch is recorded in the summary and the
+  /// remaining batches are still submitted.
+  async fn sync_inventory(
+    &self,
+    desired: &DesiredInventory,
+    options: InventorySyncOptions,
+  ) -> NeweggResult<InventorySyncSummary>;
+}
+
+#[async_trait]
+impl InventorySync for NeweggClient {
+  async fn plan_inventory_sync(
+    &self,
+    desired: &DesiredInventory,
+    options: InventorySyncOptions,
+  ) -> NeweggResult<InventoryPlan> {
+    let criteria = InventoryReportCriteria {
+      fulfill_type: options.fulfill_type,
+      ..Default::default()
+    };
+    let current: Vec<_> = self.run_report(&criteria, options.report).try_collect().await?;
+    Ok(InventoryPlan::diff(desired, &current, options.zero_missing))
+  }
+
+  async fn sync_inventory(
+    &self,
+    desired: &DesiredInventory,
+    options: InventorySyncOptions,
+  ) -> NeweggResult<InventorySyncSummary> {
+    let plan = self.plan_inventory_sync(desired, options).await?;
+    let mut summary = InventorySyncSummary::default();
+    for (i, message) in plan.batches(options.batch_size).into_iter().enumerate() {
+      let res = self.submit_feed_message(message).await.and_then(|res| {
+        res
+          .response_body
+          .response_list
+          .into_iter()
+          .next()
+          .map(|info| info.request_id)
+          .ok_or_else(|| NeweggError::Feed("no request id in submit feed response".to_string()))
+      });
+      match res {
+        Ok(request_id) => summary.feed_request_ids.push((i, request_id)),
+        Err(err) => summary.failed.push((i, err)),
+      }
+    }
+    if let Some(wait) = options.wait_for_feeds {
+      for (i, request_id) in &summary.feed_request_ids {
+        match self.wait_for_feed(request_id, wait).await {
+          Ok(report) => summary.feed_reports.push((*i, report)),
+          Err(err) => summary.failed.push((*i, err)),
+        }
+      }
+    }
+    summary.failed.sort_by_key(|(i, _)| *i);
+    summary.plan = plan;
+    Ok(summary)
+  }
+}
+
+#[test]
+fn test_inventory_plan_diff() {
+  let row = |sku: &str, location: &str, inventory: i32| InventoryReportRow {
+    seller_part_number: sku.to_string(),
+    warehouse_location: location.to_string(),
+    inventory,
+    ..Default::default()
+  };
+  let current = vec![row("a", "USA", 5), row("b", "USA", 3), row("c", "USA", 7)];
+  let mut desired = DesiredInventory::new();
+  desired.insert("a".to_string(), vec![("USA".to_string(), 5)].into_iter().collect());
+  desired.insert("b".to_string(), vec![("usa".to_string(), 1)].into_iter().collect());
+  desired.insert("d".to_string(), vec![("USA".to_string(), 2)].into_iter().collect());
+
+  let plan = InventoryPlan::diff(&desired, &current, false);
+  assert_eq!(plan.unchanged, 1);
+  assert_eq!(plan.not_listed, vec!["d"]);
+  assert!(plan.unmatched.is_empty());
+  assert_eq!(plan.changes.len(), 1);
+  assert_eq!(plan.changes[0].seller_part_number, "b");
+  assert_eq!(plan.changes[0].desired, 1);
+
+  let plan = InventoryPlan::diff(&desired, &current, true);
+  assert_eq!(plan.changes.len(), 2);
+  assert_eq!(plan.changes[1].seller_part_number, "c");
+  assert_eq!(plan.changes[1].desired, 0);
+  assert_eq!(plan.batches(1).len(), 2);
+}
+
+#[test]
+fn test_inventory_plan_diff_matches_rows_once() {
+  let row = |sku: &str, location: &str, inventory: i32| InventoryReportRow {
+    seller_part_number: sku.to_string(),
+    warehouse_location: location.to_string(),
+    inventory,
+    ..Default::default()
+  };
+  // a Canada row without location, and a US row listed with a padded location
+  let current = vec![row("a", "", 4), row("b", " usa ", 3)];
+  let mut desired = DesiredInventory::new();
+  desired.insert(
+    "a".to_string(),
+    vec![("CAN".to_string(), 4), ("USA".to_string(), 2)].into_iter().collect(),
+  );
+  desired.insert(
+    "b".to_string(),
+    vec![("Usa".to_string(), 1), ("CAN".to_string(), 6)].into_iter().collect(),
+  );
+
+  let plan = InventoryPlan::diff(&desired, &current, false);
+  // the blank row of `a` is used for one location only
+  assert_eq!(plan.unchanged, 1);
+  assert!(plan.not_listed.is_empty());
+  assert_eq!(
+    plan.unmatched,
+    vec![
+      ("a".to_string(), "USA".to_string()),
+      ("b".to_string(), "CAN".to_string()),
+    ]
+  );
+  assert_eq!(
+    plan.changes,
+    vec![InventoryChange {
+      seller_part_number: "b".to_string(),
+      warehouse_location: "USA".to_string(),
+      current: 3,
+      desired: 1,
+    }]
+  );
+
+  let plan = InventoryPlan::diff(&DesiredInventory::new(), &current, true);
+  assert_eq!(plan.changes[1].warehouse_location, "USA");
+}
@@ -1,5 +1,21 @@
 use serde_derive::{Deserialize, Serialize};
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct NeweggApiRequest<B> {
+  pub operation_type: String,
+  pub request_body: B,
+}
+
+impl<B> NeweggApiRequest<B> {
+  pub fn new(operation_type: &str, request_body: B) -> Self {
+    NeweggApiRequest {
+      operation_type: operation_type.to_string(),
+      request_body,
+    }
+  }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct NeweggApiResponse<B> {
@@ -20,9 +36,9 @@ enum IsSuccess {
 
 impl<B> NeweggApiResponse<B> {
   pub fn get_is_success(&self) -> bool {
-    match &self.is_success {
-      &IsSuccess::StringValue(ref v) => v == "true",
-      &IsSuccess::BoolValue(v) => v,
+    match self.is_success {
+      IsSuccess::StringValue(ref v) => v == "true",
+      IsSuccess::BoolValue(v) => v,
     }
   }
 }
@@ -42,9 +58,52 @@ impl<B> std::ops::Deref for NeweggApiResponseWrapped<B> {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+// `List` goes first, structs can also be deserialized from a sequence
 #[serde(untagged)]
 pub enum MaybeList<T> {
-  Single(T),
   List(Vec<T>),
+  Single(T),
+}
+
+impl<T> Default for MaybeList<T> {
+  fn default() -> Self {
+    MaybeList::List(vec![])
+  }
+}
+
+impl<T> MaybeList<T> {
+  pub fn as_slice(&self) -> &[T] {
+    match *self {
+      MaybeList::Single(ref v) => std::slice::from_ref(v),
+      MaybeList::List(ref v) => v,
+    }
+  }
+
+  pub fn into_vec(self) -> Vec<T> {
+    match self {
+      MaybeList::Single(v) => vec![v],
+      MaybeList::List(v) => v,
+    }
+  }
+}
+
+#[test]
+fn test_maybe_list_sequence() {
+  #[derive(Debug, Deserialize)]
+  struct Item {
+    #[serde(rename = "Name", default)]
+    name: String,
+  }
+
+  // a struct with only default fields also deserializes from an empty sequence
+  let list: MaybeList<Item> = serde_json::from_str("[]").unwrap();
+  assert!(list.as_slice().is_empty());
+
+  let list: MaybeList<Item> = serde_json::from_str(r#"[{"Name":"a"},{"Name":"b"}]"#).unwrap();
+  let names: Vec<_> = list.as_slice().iter().map(|v| v.name.as_str()).collect();
+  assert_eq!(names, vec!["a", "b"]);
+
+  let list: MaybeList<Item> = serde_json::from_str(r#"{"Name":"a"}"#).unwrap();
+  assert_eq!(list.into_vec()[0].name, "a");
 }
@@ -1,22 +1,43 @@
 use newegg::client::*;
 use serde::Serialize;
-use serde_json;
 use std::env;
+use std::fmt::Display;
 use std::io::stdout;
+use std::path::Path;
+use std::process;
 
+/// Unprefixed variables read when neither `NEWEGG_CONFIG` nor
+/// `NEWEGG_PLATFORM` is set, kept from before the `NEWEGG_` prefix.
+const UNPREFIXED_VARS: &[&str] = &["PLATFORM", "SELLER_ID", "TOKEN", "SECRET_KEY", "BASE_URL"];
+
+/// Reads the profile `PROFILE` of the config file `NEWEGG_CONFIG` if set,
+/// the `NEWEGG_` variables (see `NeweggClientBuilder::from_env`) if
+/// `NEWEGG_PLATFORM` is set, `UNPREFIXED_VARS` otherwise.
 pub fn get_client() -> NeweggClient {
-  NeweggClient::new(
-    match env::var("PLATFORM").unwrap().as_ref() {
-      "Newegg" => NeweggPlatform::Newegg,
-      "NeweggCanada" => NeweggPlatform::NeweggCanada,
-      "NeweggBusiness" => NeweggPlatform::NeweggBusiness,
-      v => panic!("Unknown platform: '{}'", v),
-    },
-    &env::var("SELLER_ID").unwrap(),
-    &env::var("TOKEN").unwrap(),
-    &env::var("SECRET_KEY").unwrap(),
-  )
-  .unwrap()
+  let builder = match env::var("NEWEGG_CONFIG") {
+    Ok(path) => {
+      let profile = env::var("PROFILE").ok();
+      NeweggClientBuilder::from_file(Path::new(&path), profile.as_deref())
+    }
+    Err(_) if env::var("NEWEGG_PLATFORM").is_ok() => NeweggClientBuilder::from_env(),
+    Err(_) => NeweggClientBuilder::from_vars("", |name| {
+      if UNPREFIXED_VARS.contains(&name) {
+        env::var(name).ok()
+      } else {
+        None
+      }
+    }),
+  };
+  match builder {
+    Ok(builder) => builder.build().unwrap_or_else(|err| exit_with(err)),
+    Err(err) => exit_with(err),
+  }
+}
+
+/// Prints `err` and exits with status 1.
+pub fn exit_with<E: Display>(err: E) -> ! {
+  eprintln!("{}", err);
+  process::exit(1)
 }
 
 pub fn dump_json<T: Serialize>(v: T) {
@@ -1,10 +1,7 @@
 
 use clap::clap_app;
-use newegg::NeweggPlatform;
-use serde_json::json;
 
 mod helpers;
-mod report;
 
 macro_rules! dispatch {
   ($matches:expr => $head:tt $($rest:tt)*) => {
@@ -62,10 +59,63 @@ async fn main() {
         (@arg FILE: +required "JSON file contains an order array.")
       )
     )
+    (@subcommand content =>
+      (about: "Manage items")
+      (@subcommand get_inventory =>
+        (@arg SKU: -i --sku +required +takes_value "Seller part number.")
+      )
+      (@subcommand get_price =>
+        (@arg SKU: -i --sku +required +takes_value "Seller part number.")
+      )
+    )
+    (@subcommand seller =>
+      (about: "Seller account and catalog lookups")
+      (@subcommand get_status => )
+      (@subcommand list_industries => )
+      (@subcommand list_subcategories =>
+        (@arg INDUSTRY: -i --industry +multiple +takes_value "Industry code.")
+      )
+      (@subcommand list_properties =>
+        (@arg SUBCATEGORY_ID: -s --subcategory +required +takes_value "Subcategory ID.")
+      )
+      (@subcommand list_property_values =>
+        (@arg SUBCATEGORY_ID: -s --subcategory +required +takes_value "Subcategory ID.")
+        (@arg PROPERTY: -p --property +takes_value "Property name.")
+      )
+      (@subcommand lookup_manufacturer =>
+        (@arg NAME: +required "Company name.")
+      )
+    )
+    (@subcommand rma =>
+      (about: "Manage RMAs")
+      (@subcommand list =>
+        (@arg PAGE: -p --page +takes_value "Page index.")
+      )
+      (@subcommand get =>
+        (@arg RMA_NUMBER: -r --rma +required +takes_value "RMA number.")
+      )
+    )
+    (@subcommand sbn =>
+      (about: "Shipped by Newegg inbound shipments")
+      (@subcommand list =>
+        (@arg PAGE: -p --page +takes_value "Page index.")
+      )
+      (@subcommand get_status =>
+        (@arg ID: -i --id +required +takes_value "Request ID.")
+      )
+      (@subcommand get_label =>
+        (@arg SHIPMENT_ID: -s --shipment +required +takes_value "Shipment ID.")
+        (@arg OUTPUT: +required "Output directory.")
+      )
+    )
     (@subcommand report =>
       (@subcommand submit =>
         (@arg REPORT_TYPE: -t --type +required +takes_value "Report Type.")
       )
+      (@subcommand run =>
+        (about: "Submit a report, wait for it and print the rows")
+        (@arg REPORT_TYPE: -t --type +required +takes_value "Report Type.")
+      )
       (@subcommand get_status =>
         (@arg ID: -i --id +required +multiple +takes_value "Report Request ID.")
       )
@@ -77,10 +127,10 @@ async fn main() {
         (@arg URL: -u --url +required +takes_value "URL.")
       )
       (@subcommand test_parse_us =>
-        (@arg FILE: +required "CSV file to parse.")
+        (@arg FILE: +required "Report file to parse.")
       )
       (@subcommand test_parse_ca =>
-        (@arg FILE: +required "CSV file to parse.")
+        (@arg FILE: +required "Report file to parse.")
       )
     )
     (@subcommand feed =>
@@ -98,7 +148,7 @@ async fn main() {
 
   let env = matches.value_of("ENV").unwrap_or(".env");
 
-  ::dotenv::from_filename(&env).unwrap();
+  ::dotenv::from_filename(env).unwrap();
 
   dispatch! {
     matches =>
@@ -107,7 +157,7 @@ async fn main() {
           use newegg::service_status::*;
           let client = helpers::get_client();
           let domain_str = m.value_of("DOMAIN").unwrap();
-          let domain = ServiceStatusDomain::from_str(&domain_str).ok_or_else(|| {
+          let domain = ServiceStatusDomain::from_str(domain_str).ok_or_else(|| {
             format!("Unknown domain: '{}'", domain_str)
           }).unwrap();
           let res = await_unwrap!(client.get_service_status(domain));
@@ -143,13 +193,13 @@ async fn main() {
             let action = ShipOrderAction::new(client.seller_id(), order_id)
               .add_package(
                 Package::new(ShipCarrier::Other(carrier.to_owned()), carrier, tracking)
-                  .add_item(&sku, 1)
+                  .add_item(sku, 1)
                   .finalize().unwrap()
               )
               .finalize();
             println!("Request:");
             helpers::dump_json(&action);
-            print!("\n");
+            println!();
             println!("Response:");
             let res = await_unwrap!(client.ship_order(order_id, &action));
             helpers::dump_json(res);
@@ -159,68 +209,27 @@ async fn main() {
         (download_orders =>
           (|m| {
             use newegg::order::*;
-            use chrono::{DateTime, Utc};
+            use futures::TryStreamExt;
+            use std::{fs, time::Duration};
             let client = helpers::get_client();
             let output_path = m.value_of("OUTPUT").unwrap();
-            use std::{
-              time,
-              fs,
-              thread::sleep
-            };
-
-            let mut page = 1;
-            let mut infos = vec![];
-
-            loop {
-              println!("Downloading page {}...", page);
-
-              let req = GetOrderInfoRequest::new()
-                .page_index(page)
-                .page_size(30)
-                .finalize();
-
-              let res = await_unwrap!(client.get_order_info(&req));
-
-              println!("total = {}, page_total = {}", res.total(), res.len());
-
-              let mut min_date: Option<DateTime<Utc>> = None;
-              let mut max_date: Option<DateTime<Utc>> = None;
-
-              for info in res.info_list().iter().flat_map(|v| v.iter()) {
-                let date = info.order_date.as_utc();
-                let min = min_date.get_or_insert(date);
-                let max = max_date.get_or_insert(date);
-
-                if date < *min {
-                  *min = date;
-                }
-
-                if date > *max {
-                  *max = date;
-                }
-              }
-
-              println!("min date = {:?}, max date = {:?}",
-                min_date, max_date
-              );
-
-              if res.is_empty() {
-                break
-              }
-
-              if let Some(items) = res.info_list() {
-                infos.append(&mut (items.clone() as Vec<_>));
-              }
-
-              println!("downloaded_total = {}", infos.len());
-
-              page = page + 1;
 
-              sleep(time::Duration::from_secs(1));
-            }
+            let req = GetOrderInfoRequest::new()
+              .page_size(30)
+              .finalize();
+            let options = OrderInfoStreamOptions::default()
+              .page_delay(Duration::from_secs(1));
+
+            let infos: Vec<OrderInfo> = await_unwrap!(
+              client
+                .get_order_info_stream(req, options)
+                .inspect_ok(|info| println!("downloaded order {} ({:?})", info.order_number, info.order_date.as_utc()))
+                .try_collect()
+            );
 
+            println!("downloaded_total = {}", infos.len());
             println!("Saving to {}...", output_path);
-            fs::write(&output_path, serde_json::to_string_pretty(&infos).unwrap()).unwrap();
+            fs::write(output_path, serde_json::to_string_pretty(&infos).unwrap()).unwrap();
           })
         )
 
@@ -252,6 +261,170 @@ async fn main() {
         )
       )
 
+      (content =>
+        (get_inventory =>
+          (|m| {
+            use newegg::content::*;
+            let client = helpers::get_client();
+            let sku = m.value_of("SKU").unwrap();
+            let req = GetItemInventoryRequest::new(ItemIdentifierType::SellerPartNumber, sku);
+            let res = await_unwrap!(client.get_item_inventory(&req));
+            helpers::dump_json(res);
+          })
+        )
+        (get_price =>
+          (|m| {
+            use newegg::content::*;
+            let client = helpers::get_client();
+            let sku = m.value_of("SKU").unwrap();
+            let req = GetItemPriceRequest::new(ItemIdentifierType::SellerPartNumber, sku);
+            let res = await_unwrap!(client.get_item_price(&req));
+            helpers::dump_json(res);
+          })
+        )
+      )
+
+      (seller =>
+        (get_status =>
+          (|_| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let res = await_unwrap!(client.get_seller_status());
+            helpers::dump_json(res);
+          })
+        )
+        (list_industries =>
+          (|_| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let res = await_unwrap!(client.get_industry_list());
+            helpers::dump_json(res);
+          })
+        )
+        (list_subcategories =>
+          (|m| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let mut builder = GetSubcategoryRequest::new();
+            if let Some(codes) = m.values_of("INDUSTRY") {
+              builder.industry_code_list(codes.map(ToString::to_string).collect());
+            }
+            let res = await_unwrap!(client.get_subcategory_list(&builder.finalize()));
+            helpers::dump_json(res);
+          })
+        )
+        (list_properties =>
+          (|m| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let id: i64 = m.value_of("SUBCATEGORY_ID").unwrap().parse().unwrap();
+            let res = await_unwrap!(client.get_subcategory_property_list(id));
+            helpers::dump_json(res);
+          })
+        )
+        (list_property_values =>
+          (|m| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let id: i64 = m.value_of("SUBCATEGORY_ID").unwrap().parse().unwrap();
+            let res = await_unwrap!(client.get_property_value_list(id, m.value_of("PROPERTY")));
+            helpers::dump_json(res);
+          })
+        )
+        (lookup_manufacturer =>
+          (|m| {
+            use newegg::seller::*;
+            let client = helpers::get_client();
+            let req = ManufacturerLookupRequest::new()
+              .company_name(m.value_of("NAME").unwrap())
+              .finalize();
+            let res = await_unwrap!(client.lookup_manufacturer(&req));
+            helpers::dump_json(res);
+          })
+        )
+      )
+
+      (rma =>
+        (list =>
+          (|m| {
+            use newegg::rma::*;
+            let client = helpers::get_client();
+            let page: i32 = m.value_of("PAGE").unwrap_or("1").parse().unwrap();
+            let res = await_unwrap!(client.get_rma_info(
+              &GetRmaInfoRequest::new()
+                .page_index(page)
+                .finalize()
+            ));
+            helpers::dump_json(res);
+          })
+        )
+        (get =>
+          (|m| {
+            use newegg::rma::*;
+            let client = helpers::get_client();
+            let rma_number = m.value_of("RMA_NUMBER").unwrap();
+            let res = await_unwrap!(client.get_rma_info(
+              &GetRmaInfoRequest::new()
+                .keywords(RmaKeywordsType::RmaNumber, rma_number)
+                .finalize()
+            ));
+            helpers::dump_json(res);
+          })
+        )
+      )
+
+      (sbn =>
+        (list =>
+          (|m| {
+            use newegg::sbn::*;
+            let client = helpers::get_client();
+            let page: i32 = m.value_of("PAGE").unwrap_or("1").parse().unwrap();
+            let res = await_unwrap!(client.get_inbound_shipment_list(
+              &GetInboundShipmentListRequest::new()
+                .page_index(page)
+                .finalize()
+            ));
+            helpers::dump_json(res);
+          })
+        )
+        (get_status =>
+          (|m| {
+            use newegg::sbn::*;
+            let client = helpers::get_client();
+            let res = await_unwrap!(client.get_inbound_shipment_status(m.value_of("ID").unwrap()));
+            helpers::dump_json(res);
+          })
+        )
+        (get_label =>
+          (|m| {
+            use newegg::sbn::*;
+            use std::path::Path;
+            let client = helpers::get_client();
+            let shipment_id = m.value_of("SHIPMENT_ID").unwrap();
+            let output = Path::new(m.value_of("OUTPUT").unwrap());
+            let res = await_unwrap!(client.get_package_label(shipment_id));
+            for (i, label) in res.response_body.label_list.iter().enumerate() {
+              // never let the server pick a path outside of OUTPUT
+              let name = label
+                .file_name
+                .as_deref()
+                .and_then(|name| Path::new(name).file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| {
+                  let ext: String = label.file_type.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+                  format!("{}-{}.{}", shipment_id, i, ext.to_lowercase())
+                });
+              println!("Saving {}...", name);
+              let data = label
+                .decode()
+                .unwrap_or_else(|err| helpers::exit_with(format!("decode label {}: {}", name, err)));
+              std::fs::write(output.join(&name), data)
+                .unwrap_or_else(|err| helpers::exit_with(format!("write label {}: {}", name, err)));
+            }
+          })
+        )
+      )
+
       (report =>
         (submit =>
           (|m| {
@@ -259,52 +432,38 @@ async fn main() {
             let client = helpers::get_client();
             let report_type: &str = m.value_of("REPORT_TYPE").unwrap();
 
-            let (operation_type, body) = match report_type {
-              "inventory" => {
-                match client.get_platform() {
-                  NeweggPlatform::Newegg => (
-                    "InternationalInventoryReportRequest",
-                    json!({
-                      "DailyInventoryReportCriteria": {
-                        "FulfillType": "0",
-                        "RequestType": "INTERNATIONAL_INVENTORY_REPORT",
-                        "FileType": "CSV"
-                      }
-                    })
-                  ),
-                  _ => (
-                    "DailyInventoryReportRequest",
-                    json!({
-                      "DailyInventoryReportCriteria": {
-                        "FulfillType": "0",
-                        "RequestType": "DAILY_INVENTORY_REPORT",
-                        "FileType": "CSV"
-                      }
-                    })
-                  )
-                }
-              },
-              "price" => (
-                "InternationalPriceReportRequest",
-                json!({
-                  "DailyPriceReportCriteria": {
-                    "RequestType": "INTERNATIONAL_PRICE_REPORT",
-                    "FileType": "CSV"
-                  }
-                })
-              ),
+            let platform = client.get_platform();
+            let req = match report_type {
+              "inventory" => ReportRequest::from_criteria(&InventoryReportCriteria::default(), platform),
+              "price" => ReportRequest::from_criteria(&PriceReportCriteria::default(), platform),
               other => panic!("unknown report type: '{}'", other)
-            };
+            }.unwrap();
 
             println!("Request:");
-            let req = ReportRequest::new(operation_type, body);
             helpers::dump_json(&req);
-            print!("\n");
+            println!();
             let res = await_unwrap!(client.submit_report_request(req));
             println!("Response:");
             helpers::dump_json(res);
           })
         )
+        (run =>
+          (|m| {
+            use futures::TryStreamExt;
+            use newegg::report::*;
+            let client = helpers::get_client();
+            let options = RunReportOptions::default();
+            match m.value_of("REPORT_TYPE").unwrap() {
+              "inventory" => helpers::dump_json(await_unwrap!(
+                client.run_report(&InventoryReportCriteria::default(), options).try_collect::<Vec<_>>()
+              )),
+              "price" => helpers::dump_json(await_unwrap!(
+                client.run_report(&PriceReportCriteria::default(), options).try_collect::<Vec<_>>()
+              )),
+              other => panic!("unknown report type: '{}'", other)
+            }
+          })
+        )
         (get_status =>
           (|m| {
             use newegg::report::*;
@@ -340,30 +499,28 @@ async fn main() {
         )
         (test_parse_us =>
           (|m| {
-            use std::iter::FromIterator;
+            use newegg::report::{parse, InventoryReportRow};
             let path = m.value_of("FILE").unwrap();
 
-            println!("Loading csv file: {}", path);
+            println!("Loading report file: {}", path);
 
-            let file = std::fs::File::open(path).unwrap();
-            let rows = Result::<Vec<report::InventoryReportRow>, csv::Error>::from_iter(
-              csv::Reader::from_reader(file).into_deserialize(),
-            ).unwrap();
+            // CSV, TXT (tab-delimited) or XLS by extension
+            let data = std::fs::read(path).unwrap();
+            let rows = parse::parse_report_file::<InventoryReportRow>(&data, None, path).unwrap();
 
             println!("{:#?}", rows);
           })
         )
         (test_parse_ca =>
           (|m| {
-            use std::iter::FromIterator;
+            use newegg::report::{parse, CanInventoryReportRow};
             let path = m.value_of("FILE").unwrap();
 
-            println!("Loading csv file: {}", path);
+            println!("Loading report file: {}", path);
 
-            let file = std::fs::File::open(path).unwrap();
-            let rows = Result::<Vec<report::CanInventoryReportRow>, csv::Error>::from_iter(
-              csv::Reader::from_reader(file).into_deserialize(),
-            ).unwrap();
+            // CSV, TXT (tab-delimited) or XLS by extension
+            let data = std::fs::read(path).unwrap();
+            let rows = parse::parse_report_file::<CanInventoryReportRow>(&data, None, path).unwrap();
 
             println!("{:#?}", rows);
           })
@@ -377,12 +534,7 @@ async fn main() {
             let client = helpers::get_client();
             let ids: Vec<&str> = m.values_of("ID").unwrap().collect();
 
-            let req = GetRequestStatus {
-              request_id_list: RequestIdList {
-                request_id: ids.into_iter().map(ToString::to_string).collect()
-              },
-              ..Default::default()
-            };
+            let req = GetRequestStatus::new(&ids);
 
             println!("Request:");
             helpers::dump_json(&req);
@@ -0,0 +1,59 @@
+//! Sample data for the mock server.
+
+use newegg::order::OrderInfo;
+use serde_json::json;
+
+/// An unshipped order of `items`, given as (seller part number, quantity),
+/// priced at 10.00 per unit.
+pub fn order(order_number: i64, items: &[(&str, i64)]) -> OrderInfo {
+  let total: i64 = items.iter().map(|(_, qty)| qty * 10).sum();
+  let order = json!({
+    "CustomerEmailAddress": "customer@example.com",
+    "CustomerName": "Mock Customer",
+    "CustomerPhoneNumber": "555-0100",
+    "DiscountAmount": 0.0,
+    "InvoiceNumber": 0,
+    "IsAutoVoid": false,
+    "ItemInfoList": items
+      .iter()
+      .enumerate()
+      .map(|(i, (seller_part_number, qty))| json!({
+        "Description": format!("Mock item {}", seller_part_number),
+        "ExtendShippingCharge": 0.0,
+        "ExtendUnitPrice": (qty * 10) as f64,
+        "MfrPartNumber": seller_part_number,
+        "NeweggItemNumber": format!("9SIA000{:05}", i),
+        "OrderedQty": qty,
+        "SellerPartNumber": seller_part_number,
+        "ShippedQty": 0,
+        "Status": 1,
+        "StatusDescription": "Unshipped",
+        "UPCCode": "",
+        "UnitPrice": 10.0,
+      }))
+      .collect::<Vec<_>>(),
+    "OrderDate": "01/02/2024 10:30:00",
+    "OrderDownloaded": false,
+    "OrderItemAmount": total as f64,
+    "OrderNumber": order_number,
+    "OrderQty": items.iter().map(|(_, qty)| qty).sum::<i64>(),
+    "OrderStatus": 0,
+    "OrderStatusDescription": "Unshipped",
+    "OrderTotalAmount": total as f64,
+    "PackageInfoList": [],
+    "RefundAmount": 0.0,
+    "SellerID": "A001",
+    "ShipService": "Standard Shipping (5-7 business days)",
+    "ShipToAddress1": "1 Main St",
+    "ShipToAddress2": "",
+    "ShipToCityName": "City of Industry",
+    "ShipToCompany": "",
+    "ShipToCountryCode": "UNITED STATES",
+    "ShipToFirstName": "Mock",
+    "ShipToLastName": "Customer",
+    "ShipToStateCode": "CA",
+    "ShipToZipCode": "91748",
+    "ShippingAmount": 0.0,
+  });
+  serde_json::from_value(order).expect("valid order fixture")
+}
@@ -0,0 +1,284 @@
+//! A single user, in-memory FTP server covering the commands used by
+//! `newegg::transfer`. Passive mode only, no TLS.
+
+use newegg::feed::ProcessingReportMessage;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::io;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::state::State;
+
+pub(crate) async fn serve(listener: TcpListener, state: Arc<Mutex<State>>) {
+  while let Ok((stream, _)) = listener.accept().await {
+    let state = state.clone();
+    tokio::spawn(async move {
+      let _ = Session::new(state).run(stream).await;
+    });
+  }
+}
+
+struct Session {
+  state: Arc<Mutex<State>>,
+  username: Option<String>,
+  logged_in: bool,
+  offset: u64,
+  passive: Option<TcpListener>,
+}
+
+impl Session {
+  fn new(state: Arc<Mutex<State>>) -> Self {
+    Session {
+      state,
+      username: None,
+      logged_in: false,
+      offset: 0,
+      passive: None,
+    }
+  }
+
+  async fn run(mut self, stream: TcpStream) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    reply(&mut writer, 220, "Newegg mock FTP").await?;
+    let mut line = String::new();
+    loop {
+      line.clear();
+      if reader.read_line(&mut line).await? == 0 {
+        return Ok(());
+      }
+      let line = line.trim_end();
+      let (command, arg) = match line.split_once(' ') {
+        Some((command, arg)) => (command.to_uppercase(), arg.trim().to_string()),
+        None => (line.to_uppercase(), String::new()),
+      };
+
+      match command.as_str() {
+        "USER" => {
+          self.username = Some(arg);
+          reply(&mut writer, 331, "Password required").await?;
+        }
+        "PASS" => {
+          let valid = {
+            let state = self.state.lock().unwrap();
+            self.username.as_ref() == Some(&state.options.ftp_username)
+              && arg == state.options.ftp_password
+          };
+          if valid {
+            self.logged_in = true;
+            reply(&mut writer, 230, "Logged in").await?;
+          } else {
+            reply(&mut writer, 530, "Login incorrect").await?;
+          }
+        }
+        "QUIT" => {
+          reply(&mut writer, 221, "Bye").await?;
+          return Ok(());
+        }
+        _ if !self.logged_in => reply(&mut writer, 530, "Not logged in").await?,
+        "TYPE" => reply(&mut writer, 200, "Type set").await?,
+        "NOOP" => reply(&mut writer, 200, "OK").await?,
+        "PWD" => reply(&mut writer, 257, "\"/\"").await?,
+        "CWD" | "MKD" => reply(&mut writer, 250, "OK").await?,
+        "PASV" => {
+          let listener = TcpListener::bind("127.0.0.1:0").await?;
+          let port = listener.local_addr()?.port();
+          self.passive = Some(listener);
+          let msg = format!("Entering Passive Mode (127,0,0,1,{},{})", port >> 8, port & 0xff);
+          reply(&mut writer, 227, &msg).await?;
+        }
+        "EPSV" => {
+          let listener = TcpListener::bind("127.0.0.1:0").await?;
+          let port = listener.local_addr()?.port();
+          self.passive = Some(listener);
+          let msg = format!("Entering Extended Passive Mode (|||{}|)", port);
+          reply(&mut writer, 229, &msg).await?;
+        }
+        "REST" => match arg.parse() {
+          Ok(offset) => {
+            self.offset = offset;
+            reply(&mut writer, 350, "Restarting").await?;
+          }
+          Err(_) => reply(&mut writer, 501, "Invalid offset").await?,
+        },
+        "SIZE" => {
+          let size = self.state.lock().unwrap().files.get(&normalize(&arg)).map(Vec::len);
+          match size {
+            Some(size) => reply(&mut writer, 213, &size.to_string()).await?,
+            None => reply(&mut writer, 550, "No such file").await?,
+          }
+        }
+        "RETR" => {
+          let offset = std::mem::take(&mut self.offset) as usize;
+          let data = self.state.lock().unwrap().files.get(&normalize(&arg)).cloned();
+          match data {
+            Some(data) => {
+              let data = data.get(offset..).unwrap_or_default().to_vec();
+              self.send_data(&mut writer, &data).await?;
+            }
+            None => {
+              self.passive = None;
+              reply(&mut writer, 550, "No such file").await?;
+            }
+          }
+        }
+        "NLST" | "LIST" => {
+          let dir = normalize(&arg);
+          let dir = dir.trim_end_matches('/');
+          let names: String = self
+            .state
+            .lock()
+            .unwrap()
+            .files
+            .keys()
+            .filter_map(|path| {
+              let (parent, name) = path.rsplit_once('/')?;
+              if parent == dir {
+                Some(format!("{}\r\n", name))
+              } else {
+                None
+              }
+            })
+            .collect();
+          self.send_data(&mut writer, names.as_bytes()).await?;
+        }
+        "STOR" => {
+          let path = normalize(&arg);
+          let mut data = vec![];
+          match self.accept().await {
+            Some(mut stream) => {
+              reply(&mut writer, 150, "Ready to receive").await?;
+              stream.read_to_end(&mut data).await?;
+            }
+            None => {
+              reply(&mut writer, 425, "Use PASV first").await?;
+              continue;
+            }
+          }
+          {
+            let mut state = self.state.lock().unwrap();
+            add_processing_report(&mut state, &path, &data);
+            state.files.insert(path, data);
+          }
+          reply(&mut writer, 226, "Transfer complete").await?;
+        }
+        "DELE" => {
+          let removed = self.state.lock().unwrap().files.remove(&normalize(&arg));
+          match removed {
+            Some(_) => reply(&mut writer, 250, "Deleted").await?,
+            None => reply(&mut writer, 550, "No such file").await?,
+          }
+        }
+        _ => reply(&mut writer, 502, "Command not implemented").await?,
+      }
+    }
+  }
+
+  async fn accept(&mut self) -> Option<TcpStream> {
+    let listener = self.passive.take()?;
+    listener.accept().await.ok().map(|(stream, _)| stream)
+  }
+
+  async fn send_data(&mut self, writer: &mut OwnedWriteHalf, data: &[u8]) -> io::Result<()> {
+    match self.accept().await {
+      Some(mut stream) => {
+        reply(writer, 150, "Opening data connection").await?;
+        stream.write_all(data).await?;
+        stream.shutdown().await?;
+        reply(writer, 226, "Transfer complete").await
+      }
+      None => reply(writer, 425, "Use PASV first").await,
+    }
+  }
+}
+
+async fn reply(writer: &mut OwnedWriteHalf, code: u16, msg: &str) -> io::Result<()> {
+  writer
+    .write_all(format!("{} {}\r\n", code, msg).as_bytes())
+    .await
+}
+
+fn normalize(path: &str) -> String {
+  if path.starts_with('/') {
+    path.to_string()
+  } else {
+    format!("/{}", path)
+  }
+}
+
+/// Feeds dropped in an `Inbound` folder get a processing report in the
+/// matching `Outbound` folder right away.
+fn add_processing_report(state: &mut State, path: &str, data: &[u8]) {
+  let (dir, file_name) = match path.rsplit_once('/') {
+    Some(v) => v,
+    None => return,
+  };
+  if !dir.contains("/Inbound/") {
+    return;
+  }
+  let data = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+  let text = String::from_utf8_lossy(data);
+  let (seller_part_numbers, order_numbers) = if file_name.ends_with(".csv") {
+    (csv_column(&text, "Seller Part #"), vec![])
+  } else {
+    match feed_keys(&text) {
+      Ok(keys) => keys,
+      Err(_) => return,
+    }
+  };
+  let report = ProcessingReportMessage {
+    processing_report: state.processing_report_for(file_name, seller_part_numbers, order_numbers),
+  };
+  if let Ok(xml) = report.to_xml() {
+    let stem = file_name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(file_name);
+    let report_path = format!(
+      "{}/{}_Report.xml",
+      dir.replacen("/Inbound/", "/Outbound/", 1),
+      stem
+    );
+    state.files.insert(report_path, xml.into_bytes());
+  }
+}
+
+/// Text of every `SellerPartNumber` and `OrderNumber` element of a feed file.
+fn feed_keys(xml: &str) -> Result<(Vec<String>, Vec<String>), quick_xml::Error> {
+  let mut reader = Reader::from_str(xml);
+  let mut seller_part_numbers = vec![];
+  let mut order_numbers = vec![];
+  let mut current: Option<Vec<u8>> = None;
+  loop {
+    match reader.read_event()? {
+      Event::Start(e) => current = Some(e.name().as_ref().to_vec()),
+      Event::End(_) => current = None,
+      Event::Text(e) => {
+        let text = e.unescape()?.trim().to_string();
+        match current.as_deref() {
+          Some(b"SellerPartNumber") => seller_part_numbers.push(text),
+          Some(b"OrderNumber") => order_numbers.push(text),
+          _ => {}
+        }
+      }
+      Event::Eof => return Ok((seller_part_numbers, order_numbers)),
+      _ => {}
+    }
+  }
+}
+
+/// Values of the `name` column of a CSV feed file without quoted fields.
+fn csv_column(csv: &str, name: &str) -> Vec<String> {
+  let mut lines = csv.lines();
+  let index = match lines
+    .next()
+    .and_then(|header| header.split(',').position(|column| column.trim() == name))
+  {
+    Some(index) => index,
+    None => return vec![],
+  };
+  lines
+    .filter_map(|line| line.split(',').nth(index))
+    .map(|value| value.trim().to_string())
+    .collect()
+}
@@ -0,0 +1,110 @@
+//! Just enough HTTP/1.1 to serve reqwest: one request per connection,
+//! `Content-Length` bodies only.
+
+use std::collections::HashMap;
+use std::io;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use url::Url;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+  pub method: String,
+  pub path: String,
+  pub query: HashMap<String, String>,
+  /// Header names are lowercase
+  pub headers: HashMap<String, String>,
+  pub body: Vec<u8>,
+}
+
+impl Request {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self.headers.get(name).map(AsRef::as_ref)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+  pub status: u16,
+  pub body: String,
+}
+
+impl Response {
+  pub fn new(status: u16, body: String) -> Self {
+    Response { status, body }
+  }
+}
+
+pub async fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+  let mut reader = BufReader::new(stream);
+  let mut line = String::new();
+  reader.read_line(&mut line).await?;
+  let mut parts = line.split_whitespace();
+  let (method, target) = match (parts.next(), parts.next()) {
+    (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+    _ => return Err(invalid_data(format!("bad request line: {:?}", line))),
+  };
+
+  let mut headers = HashMap::new();
+  loop {
+    line.clear();
+    if reader.read_line(&mut line).await? == 0 {
+      return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let header = line.trim_end();
+    if header.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+    }
+  }
+
+  let len = match headers.get("content-length") {
+    Some(v) => v
+      .parse::<usize>()
+      .map_err(|_| invalid_data(format!("bad content length: {}", v)))?,
+    None => 0,
+  };
+  let mut body = vec![0; len];
+  reader.read_exact(&mut body).await?;
+
+  let url = Url::parse(&format!("http://mock{}", target))
+    .map_err(|err| invalid_data(err.to_string()))?;
+  Ok(Request {
+    method,
+    path: url.path().to_string(),
+    query: url.query_pairs().into_owned().collect(),
+    headers,
+    body,
+  })
+}
+
+pub async fn write_response(stream: &mut TcpStream, res: &Response) -> io::Result<()> {
+  let head = format!(
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+    res.status,
+    reason(res.status),
+    res.body.len()
+  );
+  stream.write_all(head.as_bytes()).await?;
+  stream.write_all(res.body.as_bytes()).await?;
+  stream.shutdown().await
+}
+
+fn reason(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    400 => "Bad Request",
+    401 => "Unauthorized",
+    404 => "Not Found",
+    429 => "Too Many Requests",
+    500 => "Internal Server Error",
+    503 => "Service Unavailable",
+    _ => "Unknown",
+  }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg)
+}
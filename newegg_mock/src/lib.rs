@@ -0,0 +1,230 @@
+//! A local stand-in for the Newegg Marketplace API.
+//!
+//! `MockServer` serves the order, feed, report and service status endpoints
+//! over HTTP, with an embedded FTP server for report files and feed uploads,
+//! so code built on `newegg` can be tested without a seller account.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use newegg::order::{GetOrderInfoRequest, OrderApi};
+//! use newegg::NeweggPlatform;
+//! use newegg_mock::{fixtures, MockServer};
+//!
+//! let server = MockServer::start().await?;
+//! server.add_order(fixtures::order(1001, &[("sku-1", 2)]));
+//! let client = server.client(NeweggPlatform::Newegg)?;
+//! let res = client.get_order_info(&GetOrderInfoRequest::new().finalize()).await?;
+//! assert_eq!(res.total(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+#![allow(clippy::result_large_err)]
+
+use newegg::client::NeweggClient;
+use newegg::order::OrderInfo;
+use newegg::result::NeweggResult;
+use newegg::service_status::ServiceStatusDomain;
+use newegg::NeweggPlatform;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+mod ftp;
+mod http;
+mod routes;
+mod state;
+
+pub mod fixtures;
+
+pub use self::state::{MockFeed, MockOptions, MockReport};
+
+const LIVE_HOST: &str = "https://api.newegg.com";
+
+pub struct MockServer {
+  addr: SocketAddr,
+  ftp_addr: SocketAddr,
+  state: Arc<Mutex<state::State>>,
+  tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockServer {
+  pub async fn start() -> io::Result<Self> {
+    Self::start_with(MockOptions::default()).await
+  }
+
+  /// Binds the HTTP and FTP servers to random local ports.
+  /// Must be called inside a tokio runtime.
+  pub async fn start_with(options: MockOptions) -> io::Result<Self> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let ftp_listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let ftp_addr = ftp_listener.local_addr()?;
+
+    let mut state = state::State::new(options);
+    state.ftp_url = format!(
+      "ftp://{}:{}@{}",
+      state.options.ftp_username, state.options.ftp_password, ftp_addr
+    );
+    let state = Arc::new(Mutex::new(state));
+
+    let tasks = vec![
+      tokio::spawn(serve_http(listener, state.clone())),
+      tokio::spawn(ftp::serve(ftp_listener, state.clone())),
+    ];
+    Ok(MockServer {
+      addr,
+      ftp_addr,
+      state,
+      tasks,
+    })
+  }
+
+  /// `http://127.0.0.1:<port>`
+  pub fn url(&self) -> String {
+    format!("http://{}", self.addr)
+  }
+
+  /// The mock counterpart of `platform.base_url()`, sandbox platforms share
+  /// the production paths.
+  pub fn base_url(&self, platform: NeweggPlatform) -> String {
+    platform
+      .marketplace()
+      .base_url().replacen(LIVE_HOST, &self.url(), 1)
+  }
+
+  /// `ftp://<user>:<password>@127.0.0.1:<port>`, without a trailing slash.
+  pub fn ftp_url(&self) -> String {
+    self.state().ftp_url.clone()
+  }
+
+  pub fn ftp_addr(&self) -> SocketAddr {
+    self.ftp_addr
+  }
+
+  /// A client authenticated as the mock seller.
+  pub fn client(&self, platform: NeweggPlatform) -> NeweggResult<NeweggClient> {
+    let options = self.state().options.clone();
+    Ok(
+      NeweggClient::new(platform, &options.seller_id, &options.token, &options.secret_key)?
+        .with_base_url(&self.base_url(platform)),
+    )
+  }
+
+  pub fn add_order(&self, order: OrderInfo) {
+    let order = serde_json::to_value(order).expect("serialize order");
+    self.state().orders.push(order);
+  }
+
+  /// Current state of an order, after ship or cancel requests.
+  pub fn order(&self, order_number: i64) -> Option<OrderInfo> {
+    let order = self.state().order_mut(order_number)?.clone();
+    serde_json::from_value(order).ok()
+  }
+
+  /// Serves `data` as the file of every `request_type` report, e.g. `DAILY_INVENTORY_REPORT`.
+  pub fn set_report_file(&self, request_type: &str, file_name: &str, data: &[u8]) {
+    let path = format!("/Outbound/Report/{}", file_name);
+    let mut state = self.state();
+    state.files.insert(path.clone(), data.to_vec());
+    state.report_files.insert(request_type.to_string(), path);
+  }
+
+  /// Puts a file on the FTP server.
+  pub fn put_file(&self, path: &str, data: &[u8]) {
+    self.state().files.insert(path.to_string(), data.to_vec());
+  }
+
+  pub fn file(&self, path: &str) -> Option<Vec<u8>> {
+    self.state().files.get(path).cloned()
+  }
+
+  /// Paths of every file on the FTP server.
+  pub fn file_paths(&self) -> Vec<String> {
+    self.state().files.keys().cloned().collect()
+  }
+
+  /// Reports `errors` for a seller part number, or an order number in
+  /// order feeds, in every processing report.
+  pub fn set_item_errors(&self, key: &str, errors: &[&str]) {
+    self.state().item_errors.insert(
+      key.to_string(),
+      errors.iter().map(ToString::to_string).collect(),
+    );
+  }
+
+  pub fn set_service_status(&self, domain: ServiceStatusDomain, status: &str) {
+    self
+      .state()
+      .service_status
+      .insert(domain.as_str().to_string(), status.to_string());
+  }
+
+  /// Fails the next `times` API requests with `status`.
+  pub fn fail_next(&self, status: u16, times: usize) {
+    self.state().failures.extend(vec![status; times]);
+  }
+
+  /// Cancels a feed or report that has not finished yet.
+  pub fn cancel_request(&self, request_id: &str) {
+    use newegg::feed::RequestStatus;
+    let mut guard = self.state();
+    let state = &mut *guard;
+    let statuses = state
+      .feeds
+      .iter_mut()
+      .filter(|feed| feed.request_id == request_id)
+      .map(|feed| &mut feed.status)
+      .chain(
+        state
+          .reports
+          .iter_mut()
+          .filter(|report| report.request_id == request_id)
+          .map(|report| &mut report.status),
+      );
+    for status in statuses {
+      if *status != RequestStatus::Finished {
+        *status = RequestStatus::Cancelled;
+      }
+    }
+  }
+
+  pub fn feeds(&self) -> Vec<MockFeed> {
+    self.state().feeds.clone()
+  }
+
+  pub fn reports(&self) -> Vec<MockReport> {
+    self.state().reports.clone()
+  }
+
+  /// Every API request received, as `METHOD /path`.
+  pub fn requests(&self) -> Vec<String> {
+    self.state().requests.clone()
+  }
+
+  fn state(&self) -> MutexGuard<'_, state::State> {
+    self.state.lock().unwrap()
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    for task in &self.tasks {
+      task.abort();
+    }
+  }
+}
+
+async fn serve_http(listener: TcpListener, state: Arc<Mutex<state::State>>) {
+  while let Ok((mut stream, _)) = listener.accept().await {
+    let state = state.clone();
+    tokio::spawn(async move {
+      if let Ok(req) = http::read_request(&mut stream).await {
+        let res = routes::handle(&mut state.lock().unwrap(), &req);
+        let _ = http::write_response(&mut stream, &res).await;
+      }
+    });
+  }
+}
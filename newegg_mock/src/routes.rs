@@ -0,0 +1,538 @@
+use newegg::feed::{ProcessingReportMessage, RequestStatus, ResponseEnvelope, ResponseEnvelopeInner};
+use newegg::service_status::ServiceStatusDomain;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::http::{Request, Response};
+use crate::state::{advance, now, MockFeed, MockReport, State};
+
+const FILE_REPORTS: &[&str] = &[
+  "DAILY_INVENTORY_REPORT",
+  "DAILY_PRICE_REPORT",
+  "INTERNATIONAL_INVENTORY_REPORT",
+  "INTERNATIONAL_PRICE_REPORT",
+];
+
+pub(crate) fn handle(state: &mut State, req: &Request) -> Response {
+  state.requests.push(format!("{} {}", req.method, req.path));
+  let res = route(state, req);
+  if state.options.bom {
+    Response::new(res.status, format!("\u{feff}{}", res.body))
+  } else {
+    res
+  }
+}
+
+fn route(state: &mut State, req: &Request) -> Response {
+  if let Some(status) = state.failures.pop_front() {
+    return error(status, "CE999", "Injected failure.");
+  }
+
+  let options = &state.options;
+  if req.header("authorization") != Some(options.token.as_str())
+    || req.header("secretkey") != Some(options.secret_key.as_str())
+  {
+    return error(401, "CE002", "Unauthorized: invalid token or secret key.");
+  }
+  if req.query.get("sellerid") != Some(&options.seller_id) {
+    return error(
+      401,
+      "CE001",
+      "The specified seller id is invalid or you have not yet got authorization from this seller.",
+    );
+  }
+
+  let segments: Vec<&str> = req.path.split('/').filter(|s| !s.is_empty()).collect();
+  let domain_index = match segments
+    .iter()
+    .position(|s| ServiceStatusDomain::from_str(s).is_some())
+  {
+    Some(i) => i,
+    None => return error(404, "CE404", "Unknown API."),
+  };
+  let domain = segments[domain_index];
+  let rest = &segments[domain_index + 1..];
+
+  let body: Value = if req.body.is_empty() {
+    Value::Null
+  } else {
+    match serde_json::from_slice(&req.body) {
+      Ok(v) => v,
+      Err(err) => return error(400, "CE400", &format!("Invalid request body: {}", err)),
+    }
+  };
+
+  match (req.method.as_str(), domain, rest) {
+    ("GET", _, ["servicestatus"]) => service_status(state, domain),
+    ("PUT", "ordermgmt", ["order", "orderinfo"]) => order_info(state, &body),
+    ("PUT", "ordermgmt", ["orderstatus", "orders", order_number]) => {
+      match order_number.parse() {
+        Ok(order_number) => update_order_status(state, order_number, &body),
+        Err(_) => error(400, "SO001", "Invalid order number."),
+      }
+    }
+    ("POST", "datafeedmgmt", ["feeds", "submitfeed"]) => submit_feed(state, req, body),
+    ("PUT", "datafeedmgmt", ["feeds", "status"]) => feed_status(state, &body),
+    ("GET", "datafeedmgmt", ["feeds", "result", request_id]) => feed_result(state, request_id),
+    ("POST", "reportmgmt", ["report", "submitrequest"]) => submit_report(state, body),
+    ("PUT", "reportmgmt", ["report", "status"]) => report_status(state, &body),
+    ("PUT", "reportmgmt", ["report", "result"]) => report_result(state, &body),
+    _ => error(404, "CE404", "Unknown API."),
+  }
+}
+
+/// Error body in the shape returned by the marketplace API.
+fn error(status: u16, code: &str, message: &str) -> Response {
+  ok_status(status, json!([{ "Code": code, "Message": message }]))
+}
+
+fn ok(body: Value) -> Response {
+  ok_status(200, body)
+}
+
+fn ok_status(status: u16, body: Value) -> Response {
+  Response::new(status, body.to_string())
+}
+
+fn service_status(state: &State, domain: &str) -> Response {
+  let status = state
+    .service_status
+    .get(domain)
+    .map(AsRef::as_ref)
+    .unwrap_or("Active");
+  ok(json!({
+    "NeweggAPIResponse": {
+      "IsSuccess": "true",
+      "OperationType": "GetServiceStatusResponse",
+      "SellerID": state.options.seller_id,
+      "ResponseBody": {
+        "Status": status,
+        "Timestamp": now(),
+      }
+    }
+  }))
+}
+
+fn page_info(total_count: usize, page_index: usize, page_size: usize) -> Value {
+  let total_page_count = (0..total_count).step_by(page_size).len();
+  json!({
+    "TotalCount": total_count,
+    "TotalPageCount": total_page_count,
+    "PageIndex": page_index,
+    "PageSize": page_size,
+  })
+}
+
+fn page<T: Clone>(items: &[T], page_index: usize, page_size: usize) -> Vec<T> {
+  items
+    .iter()
+    .skip((page_index - 1) * page_size)
+    .take(page_size)
+    .cloned()
+    .collect()
+}
+
+/// Reads a number sent either as a JSON number or a string.
+fn as_usize(v: &Value) -> Option<usize> {
+  match v {
+    Value::Number(n) => n.as_u64().map(|n| n as usize),
+    Value::String(s) => s.parse().ok(),
+    _ => None,
+  }
+}
+
+/// Text of a string or number, order numbers are sent as either.
+fn as_text(v: &Value) -> Option<String> {
+  match v {
+    Value::Number(n) => Some(n.to_string()),
+    Value::String(s) => Some(s.trim().to_string()),
+    _ => None,
+  }
+}
+
+fn order_info(state: &State, body: &Value) -> Response {
+  let request_body = &body["RequestBody"];
+  let criteria = &request_body["RequestCriteria"];
+  let page_index = as_usize(&request_body["PageIndex"]).unwrap_or(1).max(1);
+  let page_size = as_usize(&request_body["PageSize"]).unwrap_or(10).max(1);
+  let order_numbers: Option<Vec<String>> = criteria["OrderNumberList"]["OrderNumber"]
+    .as_array()
+    .map(|list| list.iter().filter_map(as_text).collect());
+  let status = as_usize(&criteria["Status"]);
+
+  let orders: Vec<Value> = state
+    .orders
+    .iter()
+    .filter(|order| {
+      order_numbers
+        .as_ref()
+        .map(|list| {
+          as_text(&order["OrderNumber"])
+            .map(|number| list.contains(&number))
+            .unwrap_or(false)
+        })
+        .unwrap_or(true)
+        && status
+          .map(|status| as_usize(&order["OrderStatus"]) == Some(status))
+          .unwrap_or(true)
+    })
+    .cloned()
+    .collect();
+
+  let list = page(&orders, page_index, page_size);
+  ok(json!({
+    "IsSuccess": true,
+    "OperationType": "GetOrderInfoResponse",
+    "SellerID": state.options.seller_id,
+    "ResponseDate": now(),
+    "Memo": "",
+    "ResponseBody": {
+      "PageInfo": page_info(orders.len(), page_index, page_size),
+      "OrderInfoList": if list.is_empty() { Value::Null } else { Value::Array(list) },
+    }
+  }))
+}
+
+fn update_order_status(state: &mut State, order_number: i64, body: &Value) -> Response {
+  let seller_id = state.options.seller_id.clone();
+  let order = match state.order_mut(order_number) {
+    Some(order) => order,
+    None => return error(400, "SO002", &format!("Order {} not found.", order_number)),
+  };
+  let order_status = order["OrderStatus"].as_i64().unwrap_or(0);
+
+  match body["Action"].as_str() {
+    Some("1") => {
+      if order_status != 0 {
+        return error(400, "SO011", "Only unshipped orders can be cancelled.");
+      }
+      order["OrderStatus"] = json!(4);
+      order["OrderStatusDescription"] = json!("Voided");
+      ok(json!({
+        "IsSuccess": "true",
+        "Result": {
+          "OrderNumber": order_number.to_string(),
+          "SellerID": seller_id,
+          "OrderStatus": "Voided",
+        }
+      }))
+    }
+    Some("2") => {
+      if order_status != 0 && order_status != 1 {
+        return error(400, "SO012", "The order has already been shipped or voided.");
+      }
+      let packages = as_list(&body["Value"]["Shipment"]["PackageList"]["Package"]);
+      let ship_date = now();
+      let mut shipped: HashMap<String, i64> = HashMap::new();
+      let mut package_results = vec![];
+      for package in &packages {
+        let items: Vec<Value> = as_list(&package["ItemList"]["Item"])
+          .into_iter()
+          .map(|item| {
+            let seller_part_number = item["SellerPartNumber"].as_str().unwrap_or_default().to_string();
+            let qty = as_usize(&item["ShippedQty"]).unwrap_or(0) as i64;
+            *shipped.entry(seller_part_number.clone()).or_default() += qty;
+            json!({
+              "SellerPartNumber": seller_part_number,
+              "ShippedQty": qty,
+              "MfrPartNumber": "",
+            })
+          })
+          .collect();
+        let package_info = json!({
+          "ItemInfoList": items,
+          "PackageType": "Shipped",
+          "ShipCarrier": package["ShipCarrier"],
+          "ShipDate": ship_date,
+          "ShipService": package["ShipService"],
+          "TrackingNumber": package["TrackingNumber"],
+        });
+        if let Some(list) = order["PackageInfoList"].as_array_mut() {
+          list.push(package_info);
+        }
+        package_results.push(json!({
+          "ItemList": items
+            .iter()
+            .map(|item| json!({
+              "NeweggItemNumber": null,
+              "SellerPartNumber": item["SellerPartNumber"],
+              "ShippedQty": item["ShippedQty"],
+            }))
+            .collect::<Vec<_>>(),
+          "ProcessResult": "Succeed",
+          "ProcessStatus": true,
+          "ShipDate": ship_date,
+          "TrackingNumber": package["TrackingNumber"],
+        }));
+      }
+
+      let mut fully_shipped = true;
+      if let Some(items) = order["ItemInfoList"].as_array_mut() {
+        for item in items {
+          let seller_part_number = item["SellerPartNumber"].as_str().unwrap_or_default().to_string();
+          let qty = item["ShippedQty"].as_i64().unwrap_or(0)
+            + shipped.get(&seller_part_number).cloned().unwrap_or(0);
+          item["ShippedQty"] = json!(qty);
+          if qty < item["OrderedQty"].as_i64().unwrap_or(0) {
+            fully_shipped = false;
+          }
+        }
+      }
+      let (status, description) = if fully_shipped {
+        (2, "Shipped")
+      } else {
+        (1, "Partially Shipped")
+      };
+      order["OrderStatus"] = json!(status);
+      order["OrderStatusDescription"] = json!(description);
+
+      ok(json!({
+        "IsSuccess": true,
+        "PackageProcessingSummary": {
+          "FailCount": 0,
+          "SuccessCount": packages.len(),
+          "TotalPackageCount": packages.len(),
+        },
+        "Result": {
+          "OrderNumber": order_number.to_string(),
+          "OrderStatus": description,
+          "SellerID": seller_id,
+          "Shipment": {
+            "PackageList": package_results,
+          }
+        }
+      }))
+    }
+    _ => error(400, "SO003", "Invalid action."),
+  }
+}
+
+fn as_list(v: &Value) -> Vec<Value> {
+  match v {
+    Value::Array(items) => items.clone(),
+    Value::Null => vec![],
+    other => vec![other.clone()],
+  }
+}
+
+fn request_ids(body: &Value) -> Vec<String> {
+  as_list(&body["RequestBody"]["GetRequestStatus"]["RequestIDList"]["RequestID"])
+    .into_iter()
+    .filter_map(|v| v.as_str().map(ToString::to_string))
+    .collect()
+}
+
+fn status_str(status: RequestStatus) -> Value {
+  serde_json::to_value(status).unwrap_or(Value::Null)
+}
+
+fn feed_response(state: &State, operation_type: &str, feeds: &[&MockFeed]) -> Response {
+  ok(json!({
+    "IsSuccess": true,
+    "OperationType": operation_type,
+    "SellerID": state.options.seller_id,
+    "ResponseBody": {
+      "ResponseList": feeds
+        .iter()
+        .map(|feed| json!({
+          "RequestDate": now(),
+          "RequestId": feed.request_id,
+          "RequestStatus": status_str(feed.status),
+          "RequestType": feed.request_type,
+        }))
+        .collect::<Vec<_>>(),
+    }
+  }))
+}
+
+fn submit_feed(state: &mut State, req: &Request, mut body: Value) -> Response {
+  let request_type = match req.query.get("requesttype") {
+    Some(v) => v.clone(),
+    None => return error(400, "DF001", "RequestType is required."),
+  };
+  let envelope = body["NeweggEnvelope"].take();
+  if envelope.is_null() {
+    return error(400, "DF002", "NeweggEnvelope is required.");
+  }
+  let feed = MockFeed {
+    request_id: state.next_request_id(),
+    request_type,
+    envelope,
+    status: RequestStatus::Submitted,
+    polls: 0,
+  };
+  let res = feed_response(state, "SubmitFeedResponse", &[&feed]);
+  state.feeds.push(feed);
+  res
+}
+
+fn feed_status(state: &mut State, body: &Value) -> Response {
+  let ids = request_ids(body);
+  let polls_until_finished = state.options.polls_until_finished;
+  for feed in state.feeds.iter_mut().filter(|feed| ids.contains(&feed.request_id)) {
+    advance(&mut feed.status, &mut feed.polls, polls_until_finished);
+  }
+  let feeds: Vec<&MockFeed> = state
+    .feeds
+    .iter()
+    .filter(|feed| ids.contains(&feed.request_id))
+    .collect();
+  feed_response(state, "GetFeedStatusResponse", &feeds)
+}
+
+fn feed_result(state: &State, request_id: &str) -> Response {
+  let feed = match state.feeds.iter().find(|feed| feed.request_id == request_id) {
+    Some(feed) => feed,
+    None => return error(400, "DF003", &format!("Request {} not found.", request_id)),
+  };
+  if feed.status != RequestStatus::Finished {
+    return error(400, "DF004", "The feed has not been processed yet.");
+  }
+  let report = state.processing_report(
+    &format!("{}.xml", feed.request_id),
+    &feed.envelope["Message"],
+  );
+  let envelope = ResponseEnvelope {
+    inner: ResponseEnvelopeInner {
+      header: vec![("DocumentVersion".to_string(), "1.0".to_string())]
+        .into_iter()
+        .collect(),
+      message_type: "ProcessingReport".to_string(),
+      message: ProcessingReportMessage {
+        processing_report: report,
+      },
+    },
+  };
+  ok(serde_json::to_value(&envelope).unwrap_or(Value::Null))
+}
+
+fn report_info(state: &State, report: &MockReport) -> Value {
+  let mut info = json!({
+    "RequestId": report.request_id,
+    "RequestType": report.request_type,
+    "RequestDate": now(),
+    "RequestStatus": status_str(report.status),
+  });
+  if report.status == RequestStatus::Finished {
+    if let Some(url) = report_file_url(state, report) {
+      info["ReportFileURL"] = json!(url);
+    }
+  }
+  info
+}
+
+fn report_file_url(state: &State, report: &MockReport) -> Option<String> {
+  if !FILE_REPORTS.contains(&report.request_type.as_str()) {
+    return None;
+  }
+  let path = state
+    .report_files
+    .get(&report.request_type)
+    .cloned()
+    .unwrap_or_else(|| format!("/Outbound/Report/{}.csv", report.request_id));
+  Some(format!("{}{}", state.ftp_url, path))
+}
+
+fn report_response(state: &State, operation_type: &str, reports: &[&MockReport]) -> Response {
+  ok(json!({
+    "IsSuccess": true,
+    "OperationType": operation_type,
+    "SellerID": state.options.seller_id,
+    "ResponseBody": {
+      "ResponseList": reports.iter().map(|report| report_info(state, report)).collect::<Vec<_>>(),
+    }
+  }))
+}
+
+fn submit_report(state: &mut State, mut body: Value) -> Response {
+  let operation_type = body["OperationType"].as_str().unwrap_or_default().to_string();
+  let criteria = body["RequestBody"]
+    .as_object_mut()
+    .and_then(|body| body.values_mut().next())
+    .map(Value::take)
+    .unwrap_or(Value::Null);
+  let request_type = match criteria["RequestType"].as_str() {
+    Some(v) => v.to_string(),
+    None => return error(400, "RP001", "RequestType is required."),
+  };
+  let report = MockReport {
+    request_id: state.next_request_id(),
+    operation_type,
+    request_type,
+    criteria,
+    status: RequestStatus::Submitted,
+    polls: 0,
+  };
+  if FILE_REPORTS.contains(&report.request_type.as_str())
+    && !state.report_files.contains_key(&report.request_type)
+  {
+    // an empty report without even a header row
+    let path = format!("/Outbound/Report/{}.csv", report.request_id);
+    state.files.insert(path, vec![]);
+  }
+  let res = report_response(state, "SubmitReportResponse", &[&report]);
+  state.reports.push(report);
+  res
+}
+
+fn report_status(state: &mut State, body: &Value) -> Response {
+  let ids = request_ids(body);
+  let polls_until_finished = state.options.polls_until_finished;
+  for report in state.reports.iter_mut().filter(|report| ids.contains(&report.request_id)) {
+    advance(&mut report.status, &mut report.polls, polls_until_finished);
+  }
+  let reports: Vec<&MockReport> = state
+    .reports
+    .iter()
+    .filter(|report| ids.contains(&report.request_id))
+    .collect();
+  report_response(state, "GetReportStatusResponse", &reports)
+}
+
+fn report_result(state: &State, body: &Value) -> Response {
+  let request_body = &body["RequestBody"];
+  let request_id = request_body["RequestID"].as_str().unwrap_or_default();
+  let report = match state.reports.iter().find(|report| report.request_id == request_id) {
+    Some(report) => report,
+    None => return error(400, "RP002", &format!("Request {} not found.", request_id)),
+  };
+  if report.status != RequestStatus::Finished {
+    return error(400, "RP003", "The report is not ready yet.");
+  }
+
+  let page_index = as_usize(&request_body["PageInfo"]["PageIndex"]).unwrap_or(1).max(1);
+  let page_size = as_usize(&request_body["PageInfo"]["PageSize"]).unwrap_or(100).max(1);
+  let mut response_body = json!({
+    "RequestID": report.request_id,
+    "RequestType": report.request_type,
+  });
+  match report.request_type.as_str() {
+    "ORDER_LIST_REPORT" | "UNSHIPPED_ORDER_REPORT" => {
+      let orders: Vec<Value> = state
+        .orders
+        .iter()
+        .filter(|order| {
+          report.request_type == "ORDER_LIST_REPORT" || order["OrderStatus"].as_i64() == Some(0)
+        })
+        .cloned()
+        .collect();
+      response_body["PageInfo"] = page_info(orders.len(), page_index, page_size);
+      response_body["OrderInfoList"] = Value::Array(page(&orders, page_index, page_size));
+    }
+    _ => {
+      response_body["PageInfo"] = page_info(0, page_index, page_size);
+      if let Some(url) = report_file_url(state, report) {
+        response_body["ReportFileURL"] = json!(url);
+      }
+    }
+  }
+
+  ok(json!({
+    "NeweggAPIResponse": {
+      "IsSuccess": "true",
+      "OperationType": report.operation_type.replace("Request", "Response"),
+      "SellerID": state.options.seller_id,
+      "ResponseBody": response_body,
+    }
+  }))
+}
@@ -0,0 +1,233 @@
+use chrono::Utc;
+use chrono_tz::US::Pacific;
+use newegg::feed::{
+  AdditionalInfo, ErrorList, ProcessingReport, ProcessingResult, ProcessingSummary, RequestStatus,
+};
+use newegg::MaybeList;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+#[derive(Debug, Clone)]
+pub struct MockOptions {
+  pub seller_id: String,
+  pub token: String,
+  pub secret_key: String,
+  /// Prefix response bodies with a UTF-8 BOM like the live API does
+  pub bom: bool,
+  /// Number of status requests before a feed or report is `FINISHED`
+  pub polls_until_finished: u32,
+  pub ftp_username: String,
+  pub ftp_password: String,
+}
+
+impl Default for MockOptions {
+  fn default() -> Self {
+    MockOptions {
+      seller_id: "A001".to_string(),
+      token: "mock-token".to_string(),
+      secret_key: "mock-secret-key".to_string(),
+      bom: true,
+      polls_until_finished: 1,
+      ftp_username: "A001".to_string(),
+      ftp_password: "mock-password".to_string(),
+    }
+  }
+}
+
+impl MockOptions {
+  pub fn seller_id(self, v: &str) -> Self {
+    Self {
+      seller_id: v.to_string(),
+      ..self
+    }
+  }
+
+  pub fn credentials(self, token: &str, secret_key: &str) -> Self {
+    Self {
+      token: token.to_string(),
+      secret_key: secret_key.to_string(),
+      ..self
+    }
+  }
+
+  pub fn bom(self, v: bool) -> Self {
+    Self { bom: v, ..self }
+  }
+
+  pub fn polls_until_finished(self, v: u32) -> Self {
+    Self {
+      polls_until_finished: v,
+      ..self
+    }
+  }
+
+  pub fn ftp_credentials(self, username: &str, password: &str) -> Self {
+    Self {
+      ftp_username: username.to_string(),
+      ftp_password: password.to_string(),
+      ..self
+    }
+  }
+}
+
+/// A feed received by `submitfeed`.
+#[derive(Debug, Clone)]
+pub struct MockFeed {
+  pub request_id: String,
+  pub request_type: String,
+  /// Content of the `NeweggEnvelope`
+  pub envelope: Value,
+  pub status: RequestStatus,
+  pub(crate) polls: u32,
+}
+
+/// A report received by `submitrequest`.
+#[derive(Debug, Clone)]
+pub struct MockReport {
+  pub request_id: String,
+  pub operation_type: String,
+  pub request_type: String,
+  pub criteria: Value,
+  pub status: RequestStatus,
+  pub(crate) polls: u32,
+}
+
+#[derive(Default)]
+pub(crate) struct State {
+  pub options: MockOptions,
+  pub ftp_url: String,
+  pub orders: Vec<Value>,
+  pub feeds: Vec<MockFeed>,
+  pub reports: Vec<MockReport>,
+  pub files: BTreeMap<String, Vec<u8>>,
+  /// Report request type → file path
+  pub report_files: HashMap<String, String>,
+  /// Seller part number or order number → error descriptions
+  pub item_errors: HashMap<String, Vec<String>>,
+  pub service_status: HashMap<String, String>,
+  pub failures: VecDeque<u16>,
+  pub requests: Vec<String>,
+  next_id: u64,
+}
+
+impl State {
+  pub fn new(options: MockOptions) -> Self {
+    State {
+      options,
+      ..Default::default()
+    }
+  }
+
+  pub fn next_request_id(&mut self) -> String {
+    self.next_id += 1;
+    format!("MOCK{:06}", self.next_id)
+  }
+
+  pub fn order_mut(&mut self, order_number: i64) -> Option<&mut Value> {
+    self
+      .orders
+      .iter_mut()
+      .find(|order| order["OrderNumber"].as_i64() == Some(order_number))
+  }
+
+  /// Processing report of a feed message, every seller part number (or
+  /// order number) succeeds unless errors were registered for it.
+  pub fn processing_report(&self, original_message_name: &str, message: &Value) -> ProcessingReport {
+    let mut seller_part_numbers = vec![];
+    collect_strings(message, "SellerPartNumber", &mut seller_part_numbers);
+    let mut order_numbers = vec![];
+    collect_strings(message, "OrderNumber", &mut order_numbers);
+    self.processing_report_for(original_message_name, seller_part_numbers, order_numbers)
+  }
+
+  /// Processing report of a feed listing `seller_part_numbers`, or
+  /// `order_numbers` if it has none.
+  pub fn processing_report_for(
+    &self,
+    original_message_name: &str,
+    seller_part_numbers: Vec<String>,
+    order_numbers: Vec<String>,
+  ) -> ProcessingReport {
+    let order_feed = seller_part_numbers.is_empty();
+    let keys = if order_feed {
+      order_numbers
+    } else {
+      seller_part_numbers
+    };
+
+    let results: Vec<ProcessingResult> = keys
+      .iter()
+      .filter_map(|key| {
+        let errors = self.item_errors.get(key)?;
+        Some(ProcessingResult {
+          additional_info: AdditionalInfo {
+            sub_category_id: None,
+            seller_part_number: if order_feed { String::new() } else { key.clone() },
+            order_number: if order_feed { Some(key.clone()) } else { None },
+            manufacturer_part_number_or_isbn: None,
+            upc: None,
+          },
+          error_list: ErrorList {
+            error_description: MaybeList::List(errors.clone()),
+          },
+        })
+      })
+      .collect();
+
+    ProcessingReport {
+      original_message_name: original_message_name.to_string(),
+      status_code: "ProcessReport".to_string(),
+      processing_summary: ProcessingSummary {
+        processed_count: keys.len().to_string(),
+        success_count: (keys.len() - results.len()).to_string(),
+        with_error_count: results.len().to_string(),
+      },
+      result: MaybeList::List(results),
+    }
+  }
+}
+
+/// Moves a feed or report one status request closer to `FINISHED`.
+pub(crate) fn advance(status: &mut RequestStatus, polls: &mut u32, polls_until_finished: u32) {
+  if *status == RequestStatus::Finished || *status == RequestStatus::Cancelled {
+    return;
+  }
+  *polls += 1;
+  *status = if *polls >= polls_until_finished {
+    RequestStatus::Finished
+  } else {
+    RequestStatus::InProgress
+  };
+}
+
+fn collect_strings(value: &Value, key: &str, out: &mut Vec<String>) {
+  match value {
+    Value::Object(map) => {
+      for (k, v) in map {
+        if k == key {
+          match v {
+            Value::String(s) => out.push(s.clone()),
+            Value::Number(n) => out.push(n.to_string()),
+            _ => {}
+          }
+        } else {
+          collect_strings(v, key, out);
+        }
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        collect_strings(item, key, out);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Current Pacific time in Newegg's `MM/DD/YYYY HH:MM:SS` format.
+pub(crate) fn now() -> String {
+  Utc::now()
+    .with_timezone(&Pacific)
+    .format("%m/%d/%Y %H:%M:%S")
+    .to_string()
+}
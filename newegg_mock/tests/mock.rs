@@ -0,0 +1,344 @@
+use futures::StreamExt;
+use newegg::accounts::NeweggAccounts;
+use newegg::client::NeweggClient;
+use newegg::feed::message::{
+  FeedMessage, InventoryUpdateFeedInventory, InventoryUpdateFeedItem, InventoryUpdateFeedMessage,
+};
+use newegg::feed::{FeedApi, FeedErrorKind, PollOptions};
+use newegg::order::*;
+use newegg::report::{InventoryReportCriteria, InventoryReportRow, ReportApi};
+use newegg::result::{NeweggApiErrorKind, NeweggError};
+use newegg::retry::RetryPolicy;
+use newegg::service_status::{ServiceStatusApi, ServiceStatusDomain};
+use newegg::sync::{DesiredInventory, InventorySync, InventorySyncOptions};
+use newegg::transfer::TransferUrl;
+use newegg::NeweggPlatform;
+use newegg_mock::{fixtures, MockOptions, MockServer};
+use std::time::Duration;
+
+fn inventory_message(items: &[(&str, i32)]) -> InventoryUpdateFeedMessage {
+  InventoryUpdateFeedMessage {
+    inventory: InventoryUpdateFeedInventory {
+      item: items
+        .iter()
+        .map(|(seller_part_number, qty)| InventoryUpdateFeedItem {
+          seller_part_number: seller_part_number.to_string(),
+          warehouse_location: "USA".to_string(),
+          inventory: qty.to_string(),
+          ..Default::default()
+        })
+        .collect(),
+    },
+  }
+}
+
+#[tokio::test]
+async fn test_mock_orders() {
+  let server = MockServer::start().await.unwrap();
+  for order_number in 1001..=1005 {
+    server.add_order(fixtures::order(order_number, &[("sku-1", 2)]));
+  }
+  let client = server.client(NeweggPlatform::Newegg).unwrap();
+
+  let request = GetOrderInfoRequest::new().page_size(2).finalize();
+  let orders: Vec<_> = client
+    .get_order_info_stream(request, OrderInfoStreamOptions::default())
+    .collect()
+    .await;
+  assert_eq!(orders.len(), 5);
+  assert!(orders.iter().all(Result::is_ok));
+  assert_eq!(server.requests().len(), 3);
+
+  let request = GetOrderInfoRequest::new()
+    .order_number_list(vec!["1002".to_string(), "1004".to_string()])
+    .finalize();
+  let orders: Vec<_> = client
+    .get_order_info_stream(request, OrderInfoStreamOptions::default())
+    .map(|order| order.unwrap().order_number)
+    .collect()
+    .await;
+  assert_eq!(orders, vec![1002, 1004]);
+
+  let package = Package::new(ShipCarrier::Ups, "Ground", "1Z0001")
+    .add_item("sku-1", 2)
+    .finalize()
+    .unwrap();
+  let action = ShipOrderAction::new("A001", 1001)
+    .add_package(package)
+    .finalize();
+  client.ship_order(1001, &action).await.unwrap();
+  let order = server.order(1001).unwrap();
+  assert_eq!(order.order_status, OrderStatus::Shipped);
+  assert_eq!(order.package_info_list[0].tracking_number, "1Z0001");
+
+  client
+    .cancel_order(1002, CancelOrderReasonCode::OutOfStock)
+    .await
+    .unwrap();
+  assert_eq!(
+    server.order(1002).unwrap().order_status,
+    OrderStatus::Voided
+  );
+  match client
+    .cancel_order(1001, CancelOrderReasonCode::OutOfStock)
+    .await
+  {
+    Err(err @ NeweggError::Request { .. }) => {
+      assert_eq!(err.api_errors()[0].code, "SO011");
+      assert_eq!(
+        err.api_error_kind(),
+        Some(NeweggApiErrorKind::InvalidStatusTransition)
+      );
+      assert!(!err.should_try_again());
+    }
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+  match client
+    .cancel_order(9999, CancelOrderReasonCode::OutOfStock)
+    .await
+  {
+    Err(err) => assert_eq!(err.api_error_kind(), Some(NeweggApiErrorKind::OrderNotFound)),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+}
+
+#[tokio::test]
+async fn test_mock_order_stream() {
+  let server = MockServer::start().await.unwrap();
+  let client = server.client(NeweggPlatform::Newegg).unwrap();
+  let request = GetOrderInfoRequest::new().page_size(2).finalize();
+
+  // no orders, a single request
+  let orders: Vec<_> = client
+    .get_order_info_stream(request.clone(), OrderInfoStreamOptions::default())
+    .collect()
+    .await;
+  assert!(orders.is_empty());
+  assert_eq!(server.requests().len(), 1);
+
+  for order_number in 1001..=1005 {
+    server.add_order(fixtures::order(order_number, &[("sku-1", 1)]));
+  }
+  let options = OrderInfoStreamOptions::default().max_pages(2);
+  let orders: Vec<_> = client
+    .get_order_info_stream(request.clone(), options)
+    .map(|res| res.unwrap().order_number)
+    .collect()
+    .await;
+  assert_eq!(orders, vec![1001, 1002, 1003, 1004]);
+  assert_eq!(server.requests().len(), 3);
+
+  // an error ends the stream
+  let mut stream = client.get_order_info_stream(request, OrderInfoStreamOptions::default());
+  assert!(stream.next().await.unwrap().is_ok());
+  server.fail_next(400, 1);
+  assert!(stream.next().await.unwrap().is_ok());
+  assert!(stream.next().await.unwrap().is_err());
+  assert!(stream.next().await.is_none());
+  assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn test_mock_inventory_sync() {
+  let server = MockServer::start_with(MockOptions::default().polls_until_finished(2))
+    .await
+    .unwrap();
+  server.set_report_file(
+    "INTERNATIONAL_INVENTORY_REPORT",
+    "inventory.csv",
+    b"\xef\xbb\xbfSeller Part #,NE Item #,Warehouse Location,Fulfillment Option,Inventory\r\n\
+      sku-1,9SIA0001,USA,Seller,5\r\n\
+      sku-2,9SIA0002,USA,Seller,3\r\n",
+  );
+  server.set_item_errors("sku-2", &["CEI0001:Can't find this item in the system"]);
+  let client = server.client(NeweggPlatform::Newegg).unwrap();
+
+  let mut desired = DesiredInventory::new();
+  desired.insert(
+    "sku-1".to_string(),
+    vec![("USA".to_string(), 5)].into_iter().collect(),
+  );
+  desired.insert(
+    "sku-2".to_string(),
+    vec![("USA".to_string(), 7)].into_iter().collect(),
+  );
+  let wait = PollOptions::default().poll_interval(Duration::from_millis(10));
+  let options = InventorySyncOptions::default()
+    .report(newegg::report::RunReportOptions::default().poll_interval(Duration::from_millis(10)))
+    .wait_for_feeds(wait);
+  let summary = client.sync_inventory(&desired, options).await.unwrap();
+  assert_eq!(summary.plan.unchanged, 1);
+  assert_eq!(summary.feed_request_ids.len(), 1);
+  assert_eq!(summary.feed_reports.len(), 1);
+  assert!(summary.failed.is_empty());
+  assert_eq!(server.feeds()[0].request_type, "INVENTORY_DATA");
+
+  let (batch, report) = &summary.feed_reports[0];
+  assert_eq!(*batch, 0);
+  assert_eq!(report.processing_summary.with_error_count, "1");
+  assert_eq!(
+    report.feed_errors_by_seller_part_number()["sku-2"][0].kind(),
+    FeedErrorKind::ItemNotFound
+  );
+
+  let res = client
+    .submit_feed_message(inventory_message(&[("sku-1", 1)]))
+    .await
+    .unwrap();
+  let request_id = &res.response_body.response_list[0].request_id;
+  server.cancel_request(request_id);
+  match client.wait_for_feed(request_id, wait).await {
+    Err(NeweggError::FeedCancelled(id)) => assert_eq!(&id, request_id),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+}
+
+#[tokio::test]
+async fn test_mock_ftp_feed_upload() {
+  let server = MockServer::start().await.unwrap();
+  let client = server.client(NeweggPlatform::Newegg).unwrap();
+  let ftp = TransferUrl::parse(&format!("{}/", server.ftp_url())).unwrap();
+
+  let envelope = inventory_message(&[("sku-1", 1), ("sku-2", 0)]).into_envelope();
+  let upload = client
+    .upload_feed(&ftp, InventoryUpdateFeedMessage::REQUEST_TYPE, envelope)
+    .await
+    .unwrap();
+  assert!(server
+    .file(&format!("/Inbound/Inventory/{}", upload.file_name))
+    .is_some());
+
+  let path = client
+    .find_feed_processing_report(&ftp, &upload)
+    .await
+    .unwrap()
+    .unwrap();
+  let report = client
+    .get_feed_processing_report(&ftp, &path)
+    .await
+    .unwrap();
+  assert_eq!(
+    report.processing_report.processing_summary.processed_count,
+    "2"
+  );
+
+  server.set_item_errors("sku-2", &["CEI0001:Can't find this item in the system"]);
+  let rows: Vec<InventoryReportRow> = ["sku-1", "sku-2"]
+    .iter()
+    .map(|sku| InventoryReportRow {
+      seller_part_number: sku.to_string(),
+      warehouse_location: "USA".to_string(),
+      inventory: 1,
+      ..Default::default()
+    })
+    .collect();
+  let upload = client
+    .upload_feed_csv(&ftp, InventoryUpdateFeedMessage::REQUEST_TYPE, &rows)
+    .await
+    .unwrap();
+  assert!(upload.file_name.ends_with(".csv"));
+  let wait = PollOptions::default().poll_interval(Duration::from_millis(10));
+  let report = client
+    .wait_for_feed_processing_report(&ftp, &upload, wait)
+    .await
+    .unwrap();
+  let summary = &report.processing_report.processing_summary;
+  assert_eq!(summary.processed_count, "2");
+  assert_eq!(summary.with_error_count, "1");
+}
+
+#[tokio::test]
+async fn test_mock_failures() {
+  let server = MockServer::start_with(MockOptions::default().bom(false))
+    .await
+    .unwrap();
+  let client = server
+    .client(NeweggPlatform::NeweggCanada)
+    .unwrap()
+    .with_retry_policy(RetryPolicy::default().base_delay(Duration::from_millis(1)));
+
+  server.fail_next(503, 1);
+  server.set_service_status(ServiceStatusDomain::Order, "Inactive");
+  client
+    .get_service_status(ServiceStatusDomain::Order)
+    .await
+    .unwrap();
+  assert_eq!(
+    server.requests(),
+    vec![
+      "GET /marketplace/can/ordermgmt/servicestatus",
+      "GET /marketplace/can/ordermgmt/servicestatus",
+    ]
+  );
+
+  // creating a report is not idempotent, only throttled requests are replayed
+  let criteria = InventoryReportCriteria::default();
+  server.fail_next(503, 1);
+  assert!(client.submit_report(&criteria).await.is_err());
+  server.fail_next(429, 1);
+  client.submit_report(&criteria).await.unwrap();
+  assert_eq!(server.requests().len(), 5);
+  assert_eq!(server.reports().len(), 1);
+
+  let client = NeweggClient::new(NeweggPlatform::Newegg, "A001", "wrong", "wrong")
+    .unwrap()
+    .with_base_url(&server.base_url(NeweggPlatform::Newegg));
+  match client.get_service_status(ServiceStatusDomain::Order).await {
+    Err(err @ NeweggError::Request { .. }) => {
+      assert_eq!(err.api_error_kind(), Some(NeweggApiErrorKind::AuthFailure))
+    }
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+
+  let client = NeweggClient::new(NeweggPlatform::Newegg, "A002", "mock-token", "mock-secret-key")
+    .unwrap()
+    .with_base_url(&server.base_url(NeweggPlatform::Newegg));
+  match client.get_service_status(ServiceStatusDomain::Order).await {
+    Err(err) => assert_eq!(err.api_error_kind(), Some(NeweggApiErrorKind::InvalidSeller)),
+    other => panic!("unexpected result: {:?}", other.map(|_| ())),
+  }
+}
+
+#[tokio::test]
+async fn test_mock_accounts() {
+  let server = MockServer::start().await.unwrap();
+  server.add_order(fixtures::order(1001, &[("sku-1", 1)]));
+  server.add_order(fixtures::order(1002, &[("sku-2", 1)]));
+
+  let mut accounts = NeweggAccounts::new().unwrap();
+  for (platform, token) in &[
+    (NeweggPlatform::Newegg, "mock-token"),
+    (NeweggPlatform::NeweggCanada, "mock-token"),
+    (NeweggPlatform::NeweggBusiness, "wrong"),
+  ] {
+    let builder = NeweggClient::builder(*platform, "A001", token, "mock-secret-key")
+      .base_url(&server.base_url(*platform));
+    accounts.add(builder).unwrap();
+  }
+  assert_eq!(accounts.len(), 3);
+  accounts
+    .get(NeweggPlatform::NeweggCanada, "A001")
+    .unwrap()
+    .cancel_order(1002, CancelOrderReasonCode::OutOfStock)
+    .await
+    .unwrap();
+
+  let merged = accounts
+    .get_unshipped_orders(OrderInfoStreamOptions::default())
+    .await;
+  let mut tagged: Vec<_> = merged
+    .orders
+    .iter()
+    .map(|o| (o.account.platform, o.order.order_number))
+    .collect();
+  tagged.sort_by_key(|(platform, _)| platform.as_str());
+  assert_eq!(
+    tagged,
+    vec![
+      (NeweggPlatform::Newegg, 1001),
+      (NeweggPlatform::NeweggCanada, 1001)
+    ]
+  );
+  assert_eq!(merged.errors.len(), 1);
+  assert_eq!(merged.errors[0].0.platform, NeweggPlatform::NeweggBusiness);
+}